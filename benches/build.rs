@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use packx::{build_memory, build_one_bump};
+
+// A fixed pubkey keeps runs comparable: table contents, and so early exits in row filling,
+// depend on it.
+const PUBKEY: [u8; 32] = [0x5C; 32];
+
+// Seed-level parallelism only pays off when few bumps are built; a full memory is better
+// split across bumps. Expect no gain on a single core.
+fn bench_build_one_bump(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_one_bump");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(20);
+
+    group.bench_function("sequential", |b| b.iter(|| black_box(build_one_bump(black_box(&PUBKEY), 0))));
+    #[cfg(feature = "rayon")]
+    group.bench_function(format!("parallel_{}_threads", rayon::current_num_threads()), |b| {
+        b.iter(|| black_box(packx::build_one_bump_parallel(black_box(&PUBKEY), 0)))
+    });
+    group.finish();
+}
+
+fn bench_build_memory(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_memory");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+
+    group.bench_function("all_bumps", |b| b.iter(|| black_box(build_memory(black_box(&PUBKEY)))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_build_one_bump, bench_build_memory);
+criterion_main!(benches);
@@ -0,0 +1,129 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use packx::{build_one_bump, covering_seeds, group_candidates};
+use rand::RngCore;
+
+/// The 8-way short-circuiting test `covers_all` replaced, as the reference for `covers_all`.
+fn covers_all_chain(present: &[u8; 32], need: &[u8; 8]) -> bool {
+    let bit = |t: u8| present[(t >> 3) as usize] & (1 << (t & 7)) != 0;
+    bit(need[0])
+        && bit(need[1])
+        && bit(need[2])
+        && bit(need[3])
+        && bit(need[4])
+        && bit(need[5])
+        && bit(need[6])
+        && bit(need[7])
+}
+
+/// `covering_seeds` built on `covers_all_chain`.
+fn covering_seeds_chain(data: &[u8; 128], g: usize, present: &[[u8; 32]]) -> [u8; 32] {
+    let need: [u8; 8] = data[g * 8..g * 8 + 8].try_into().unwrap();
+    let mut out = [0u8; 32];
+    for (seed, row) in present.iter().enumerate() {
+        out[seed >> 3] |= (covers_all_chain(row, &need) as u8) << (seed & 7);
+    }
+    out
+}
+
+fn bench_coverage(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let table = build_one_bump(&pubkey, 0);
+
+    let chunks: Vec<[u8; 128]> = (0..256)
+        .map(|_| {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            data
+        })
+        .collect();
+
+    // Both compute the covering seeds of all 16 groups of one chunk.
+    let mut group = c.benchmark_group("coverage");
+    let mut i = 0usize;
+    group.bench_function("candidates_vec", |b| {
+        b.iter(|| {
+            let data = &chunks[i % chunks.len()];
+            i += 1;
+            for g in 0..16 {
+                black_box(group_candidates(black_box(data), g, &table));
+            }
+        })
+    });
+
+    let mut i = 0usize;
+    group.bench_function("covering_seeds_bitset", |b| {
+        b.iter(|| {
+            let data = &chunks[i % chunks.len()];
+            i += 1;
+            for g in 0..16 {
+                black_box(covering_seeds(black_box(data), g, &table));
+            }
+        })
+    });
+    group.finish();
+
+
+    // Popcount over every seed's present bitset, and the scans built on it.
+    let mut group = c.benchmark_group("coverage_stats");
+    group.bench_function("coverage", |b| b.iter(|| black_box(black_box(&table).coverage())));
+    group.bench_function("fully_covered_seeds", |b| b.iter(|| black_box(black_box(&table).fully_covered_seeds())));
+    group.finish();
+}
+
+/// The branchless `covers_all`, through `covering_seeds`, against the `&&` chain it replaced,
+/// over all 16 groups of one random chunk. One run, nightly toolchain:
+///
+/// | path                                  | per chunk |
+/// |---------------------------------------|-----------|
+/// | `and_chain`                           | 46.3 µs   |
+/// | `covers_all` (scalar fold)            | 13.4 µs   |
+/// | `covers_all_simd` (`--features simd`) | 27.8 µs   |
+///
+/// The fold is about 3.5x faster than the chain, whose branches are unpredictable on random
+/// data. The 8-lane gather beats the chain but not the fold, so `simd` doesn't pay off here.
+fn bench_covers_all(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let table = build_one_bump(&pubkey, 0);
+
+    let chunks: Vec<[u8; 128]> = (0..256)
+        .map(|_| {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            data
+        })
+        .collect();
+    assert!(chunks
+        .iter()
+        .all(|data| (0..16).all(|g| covering_seeds(data, g, &table) == covering_seeds_chain(data, g, &table.present))));
+
+    let mut group = c.benchmark_group("covers_all");
+    let mut i = 0usize;
+    group.bench_function("and_chain", |b| {
+        b.iter(|| {
+            let data = &chunks[i % chunks.len()];
+            i += 1;
+            for g in 0..16 {
+                black_box(covering_seeds_chain(black_box(data), g, &table.present));
+            }
+        })
+    });
+
+    let mut i = 0usize;
+    group.bench_function(if cfg!(feature = "simd") { "covers_all_simd" } else { "covers_all" }, |b| {
+        b.iter(|| {
+            let data = &chunks[i % chunks.len()];
+            i += 1;
+            for g in 0..16 {
+                black_box(covering_seeds(black_box(data), g, &table));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_coverage, bench_covers_all);
+criterion_main!(benches);
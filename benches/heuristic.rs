@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use packx::{build_one_bump, combination_count, solve_one_bump, solve_one_bump_heuristic};
+use rand::RngCore;
+
+fn bench_heuristic(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let table = build_one_bump(&pubkey, 0);
+
+    // Only chunks this bump covers, so both solvers always find a solution.
+    let mut chunks = Vec::new();
+    while chunks.len() < 1024 {
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+        if combination_count(&data, &table) > 0 {
+            chunks.push(data);
+        }
+    }
+
+    let mut group = c.benchmark_group("one_bump");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+
+    for difficulty in [8, 12, 16] {
+        let mut i = 0usize;
+        group.bench_function(format!("odometer_difficulty_{difficulty}"), |b| {
+            b.iter(|| {
+                let data = &chunks[i % chunks.len()];
+                i += 1;
+                black_box(solve_one_bump(black_box(data), 0, &table, difficulty).expect("solve failed"));
+            })
+        });
+
+        let mut i = 0usize;
+        group.bench_function(format!("heuristic_difficulty_{difficulty}"), |b| {
+            b.iter(|| {
+                let data = &chunks[i % chunks.len()];
+                i += 1;
+                black_box(
+                    solve_one_bump_heuristic(black_box(data), 0, &table, difficulty, i as u64).expect("solve failed"),
+                );
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_heuristic);
+criterion_main!(benches);
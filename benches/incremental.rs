@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use packx::{build_one_bump, combination_count, solve_one_bump, Solution, SOLUTION_SIZE};
+use rand::RngCore;
+
+/// How the per-combination difficulty hash compares to the rest of a bump search.
+///
+/// `hash/full` is what every combination pays today. `hash/without_first_block` hashes only
+/// the 81 bytes after the first 64-byte BLAKE3 block, a lower bound on any prefix-caching
+/// scheme. It can't actually be had: the seeds sit in bytes 1..17, so advancing any group
+/// changes the first block.
+fn bench_incremental(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let table = build_one_bump(&pubkey, 0);
+
+    let mut chunks = Vec::new();
+    while chunks.len() < 1024 {
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+        if combination_count(&data, &table) > 0 {
+            chunks.push(data);
+        }
+    }
+
+    let mut solution = Solution::zeroed();
+    rng.fill_bytes(&mut solution.seeds);
+    rng.fill_bytes(&mut solution.nonces);
+
+    let mut group = c.benchmark_group("hash");
+    group.bench_function("full", |b| {
+        b.iter(|| {
+            solution.seeds[0] = solution.seeds[0].wrapping_add(1);
+            black_box(black_box(&solution).difficulty())
+        })
+    });
+    group.bench_function("without_first_block", |b| {
+        let bytes = solution.to_bytes();
+        b.iter(|| black_box(blake3::hash(black_box(&bytes[64..SOLUTION_SIZE]))))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("search");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+
+    let mut i = 0usize;
+    group.bench_function("coverage", |b| {
+        b.iter(|| {
+            let data = &chunks[i % chunks.len()];
+            i += 1;
+            black_box(combination_count(black_box(data), &table))
+        })
+    });
+    for difficulty in [0, 8, 12] {
+        let mut i = 0usize;
+        group.bench_function(format!("solve_difficulty_{difficulty}"), |b| {
+            b.iter(|| {
+                let data = &chunks[i % chunks.len()];
+                i += 1;
+                black_box(solve_one_bump(black_box(data), 0, &table, difficulty))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_incremental);
+criterion_main!(benches);
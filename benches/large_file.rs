@@ -1,4 +1,4 @@
-use packx::{build_memory, solve_with_memory};
+use packx::{build_memory, Solver};
 use rand::RngCore;
 use rayon::prelude::*;
 use std::time::Instant;
@@ -45,8 +45,9 @@ fn main() {
 
     let start = Instant::now();
 
-    chunks.par_iter().for_each(|chunk| {
-        let _ = solve_with_memory(chunk, &mem, 0)
+    // One Solver per rayon worker so candidate storage is reused across chunks.
+    chunks.par_iter().for_each_init(Solver::new, |solver, chunk| {
+        let _ = solver.solve(chunk, &mem, 0)
             .expect("No coverage across bumps (unexpected)");
     });
 
@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use packx::{build_one_bump, combination_count, solve_one_bump_ordered, OrderingStrategy};
+use rand::RngCore;
+
+fn bench_ordering(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let table = build_one_bump(&pubkey, 0);
+
+    // Only chunks this bump covers, so every strategy always finds a solution.
+    let mut chunks = Vec::new();
+    while chunks.len() < 1024 {
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+        if combination_count(&data, &table) > 0 {
+            chunks.push(data);
+        }
+    }
+
+    let strategies = [
+        ("fewest_first", OrderingStrategy::FewestFirst),
+        ("most_first", OrderingStrategy::MostFirst),
+        ("group_index", OrderingStrategy::GroupIndex),
+    ];
+
+    let mut group = c.benchmark_group("ordering");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+
+    for difficulty in [0, 4, 8, 12] {
+        for (name, ordering) in strategies {
+            let mut i = 0usize;
+            group.bench_function(format!("{name}_difficulty_{difficulty}"), |b| {
+                b.iter(|| {
+                    let data = &chunks[i % chunks.len()];
+                    i += 1;
+                    black_box(
+                        solve_one_bump_ordered(black_box(data), 0, &table, difficulty, ordering).expect("solve failed"),
+                    );
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ordering);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+//! Deterministic conformance vectors for other packx implementations.
+//!
+//! ```text
+//! cargo run --release --example gen_vectors -- <seed> [pubkeys]
+//! ```
+//!
+//! Writes one JSON object per line to stdout. `seed` (a u64) fixes every input, and
+//! `pubkeys` (default 2) sets how many pubkeys to cover; each costs one `build_memory`.
+//! For every pubkey there are `CHUNKS_PER_DIFFICULTY` chunks at each difficulty in
+//! `DIFFICULTIES`. Byte strings are lowercase hex:
+//!
+//! - `pubkey`: 32 bytes
+//! - `data`: the 128-byte chunk
+//! - `difficulty`: the minimum the solve was asked for
+//! - `solution_bytes`: the 145-byte serialized solution (bump, 16 seeds, 128 nonces)
+//! - `solution_hash`: BLAKE3 of `solution_bytes`; its leading zero bits are at least
+//!   `difficulty`
+//!
+//! Inputs come from splitmix64 seeded with `seed`, rather than a `rand` generator whose
+//! stream may change between versions, so a port can regenerate the inputs too. Chunks
+//! no bump covers are drawn again. A verifier should check that each solution unpacks
+//! to `data` under `pubkey` and that its hash matches and meets `difficulty`.
+
+use packx::{build_memory, solve_with_memory, verify};
+use serde_json::json;
+
+const DIFFICULTIES: [u32; 4] = [0, 4, 8, 12];
+const CHUNKS_PER_DIFFICULTY: usize = 2;
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn fill(state: &mut u64, out: &mut [u8]) {
+    for chunk in out.chunks_mut(8) {
+        chunk.copy_from_slice(&splitmix64(state).to_le_bytes()[..chunk.len()]);
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let usage = "usage: gen_vectors <seed> [pubkeys]";
+    let seed: u64 = args.next().and_then(|s| s.parse().ok()).expect(usage);
+    let pubkeys: usize = args.next().map_or(Some(2), |s| s.parse().ok()).expect(usage);
+
+    let mut state = seed;
+    for _ in 0..pubkeys {
+        let mut pubkey = [0u8; 32];
+        fill(&mut state, &mut pubkey);
+        let mem = build_memory(&pubkey);
+
+        for difficulty in DIFFICULTIES {
+            let mut written = 0;
+            while written < CHUNKS_PER_DIFFICULTY {
+                let mut data = [0u8; 128];
+                fill(&mut state, &mut data);
+                let Some(solution) = solve_with_memory(&data, &mem, difficulty) else { continue };
+                assert!(verify(&pubkey, &data, &solution, difficulty));
+
+                let line = json!({
+                    "pubkey": hex(&pubkey),
+                    "data": hex(&data),
+                    "difficulty": difficulty,
+                    "solution_bytes": hex(&solution.to_bytes()),
+                    "solution_hash": hex(&solution.solution_hash()),
+                });
+                println!("{line}");
+                written += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Several worker threads solving with one `SolverMemory` behind an `Arc`.
+//!
+//! ```text
+//! cargo run --release --example shared_memory
+//! ```
+//!
+//! The tables are built once and never copied: each worker gets an `Arc` clone, which only
+//! bumps a reference count. `SolverMemory` is `Send + Sync` and solving only reads it.
+
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use packx::{build_memory, solve_shared, verify};
+use rand::RngCore;
+
+const WORKERS: usize = 4;
+const CHUNKS: usize = 64;
+const DIFFICULTY: u32 = 8;
+
+fn main() {
+    let mut rng = rand::thread_rng();
+    let mut pubkey = [0u8; 32];
+    rng.fill_bytes(&mut pubkey);
+    let mem = Arc::new(build_memory(&pubkey));
+
+    let (job_tx, job_rx) = mpsc::channel::<(usize, [u8; 128])>();
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let (result_tx, result_rx) = mpsc::channel();
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let mem = Arc::clone(&mem);
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            thread::spawn(move || loop {
+                let job = job_rx.lock().unwrap().recv();
+                let Ok((index, data)) = job else { break };
+                let solution = solve_shared(&data, Arc::clone(&mem), DIFFICULTY);
+                result_tx.send((index, data, solution)).unwrap();
+            })
+        })
+        .collect();
+    drop(result_tx);
+
+    for index in 0..CHUNKS {
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+        job_tx.send((index, data)).unwrap();
+    }
+    drop(job_tx);
+
+    let mut solved = 0;
+    for (index, data, solution) in result_rx {
+        match solution {
+            Some(solution) => {
+                assert!(verify(&pubkey, &data, &solution, DIFFICULTY));
+                solved += 1;
+            }
+            None => println!("chunk {index}: no solution"),
+        }
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    println!("solved {solved}/{CHUNKS} chunks on {WORKERS} threads sharing one memory");
+}
@@ -0,0 +1,65 @@
+//! Async wrappers that run solves on tokio's blocking thread pool.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{build_memory, solve_with_memory_cancellable, Solution, SolverMemory};
+
+/// Sets the flag when dropped, so a dropped future stops its blocking search.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `solve`, run with `spawn_blocking` so the CPU-bound work doesn't stall the async executor.
+///
+/// Must be polled inside a tokio runtime. Dropping the future cancels the search within
+/// `CANCEL_POLL_INTERVAL` combinations, but a table build already underway (about a second
+/// or two) runs to completion on the blocking pool first. Resolves to None if the runtime
+/// shuts down before the blocking task starts.
+pub fn solve_async(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    difficulty: u32,
+) -> impl Future<Output = Option<Solution>> + Send + 'static {
+    let (pubkey, data) = (*pubkey, *data);
+    run_blocking(move |cancel| {
+        let mem = build_memory(&pubkey);
+        solve_with_memory_cancellable(&data, &mem, difficulty, cancel)
+    })
+}
+
+/// `solve_with_memory` on the blocking pool, sharing `mem` with other tasks through the `Arc`.
+///
+/// Must be polled inside a tokio runtime. Dropping the future cancels the search within
+/// `CANCEL_POLL_INTERVAL` combinations; the blocking thread is released shortly after.
+/// Resolves to None if the runtime shuts down before the blocking task starts.
+pub fn solve_with_memory_async(
+    data: &[u8; 128],
+    mem: Arc<SolverMemory>,
+    difficulty: u32,
+) -> impl Future<Output = Option<Solution>> + Send + 'static {
+    let data = *data;
+    run_blocking(move |cancel| solve_with_memory_cancellable(&data, &mem, difficulty, cancel))
+}
+
+/// Spawn `work` when first polled and await it, cancelling it if the future is dropped.
+/// A panic in `work` is resumed in the awaiting task; a task the runtime cancelled before it
+/// ran yields None.
+async fn run_blocking(work: impl FnOnce(&AtomicBool) -> Option<Solution> + Send + 'static) -> Option<Solution> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let guard = CancelOnDrop(Arc::clone(&cancel));
+    let result = tokio::task::spawn_blocking(move || work(&cancel)).await;
+    drop(guard);
+    match result {
+        Ok(solution) => solution,
+        Err(err) => match err.try_into_panic() {
+            Ok(payload) => std::panic::resume_unwind(payload),
+            Err(_) => None,
+        },
+    }
+}
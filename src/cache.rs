@@ -0,0 +1,88 @@
+//! A small LRU of `SolverMemory` tables keyed by pubkey.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{build_memory, SolverMemory};
+
+/// Shares built tables between requests for the same pubkey, keeping at most `capacity`
+/// memories (about 18 MiB each) alive.
+///
+/// The cache is `Sync`: share one behind an `Arc` or a `static`. Lookups take a short lock;
+/// building happens outside it, so a slow build never blocks hits on other pubkeys. Two
+/// threads missing on the same pubkey at once may both build; the first to finish is
+/// cached and both get that copy.
+///
+/// Eviction drops the least recently used entry once `capacity` is exceeded. An evicted
+/// memory stays alive for callers still holding its `Arc` and is freed with the last one.
+pub struct MemoryCache {
+    capacity: usize,
+    /// Least recently used first.
+    entries: Mutex<Vec<([u8; 32], Arc<SolverMemory>)>>,
+}
+
+impl MemoryCache {
+    /// An empty cache holding at most `capacity` memories; 0 caches nothing.
+    pub fn new(capacity: usize) -> Self {
+        MemoryCache { capacity, entries: Mutex::new(Vec::with_capacity(capacity)) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drop every cached entry.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// The cached memory for `pubkey`, marking it most recently used.
+    pub fn get(&self, pubkey: &[u8; 32]) -> Option<Arc<SolverMemory>> {
+        let mut entries = self.lock();
+        let pos = entries.iter().position(|(key, _)| key == pubkey)?;
+        let entry = entries.remove(pos);
+        let mem = Arc::clone(&entry.1);
+        entries.push(entry);
+        Some(mem)
+    }
+
+    /// The cached memory for `pubkey`, building it with `build_memory` on a miss.
+    pub fn get_or_build(&self, pubkey: &[u8; 32]) -> Arc<SolverMemory> {
+        self.get_or_build_with(pubkey, || build_memory(pubkey))
+    }
+
+    /// `get_or_build` with a custom builder for misses, e.g. loading saved tables or
+    /// building with non-default hash parameters.
+    pub fn get_or_build_with(&self, pubkey: &[u8; 32], build: impl FnOnce() -> SolverMemory) -> Arc<SolverMemory> {
+        if let Some(mem) = self.get(pubkey) {
+            return mem;
+        }
+        let built = Arc::new(build());
+        if self.capacity == 0 {
+            return built;
+        }
+
+        let mut entries = self.lock();
+        if let Some((_, mem)) = entries.iter().find(|(key, _)| key == pubkey) {
+            return Arc::clone(mem);
+        }
+        if entries.len() == self.capacity {
+            entries.remove(0);
+        }
+        entries.push((*pubkey, Arc::clone(&built)));
+        built
+    }
+
+    /// The entry list, recovering it if a panicking thread poisoned the lock; every
+    /// operation leaves the list consistent, so its contents are still valid.
+    fn lock(&self) -> MutexGuard<'_, Vec<([u8; 32], Arc<SolverMemory>)>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
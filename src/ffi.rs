@@ -0,0 +1,92 @@
+//! C ABI for embedding packx in non-Rust miners.
+//!
+//! Ownership: `packx_build_memory` returns a heap-allocated `SolverMemory` (about 18 MiB) owned by
+//! the caller, who must release it exactly once with `packx_free_memory`. The handle can be reused
+//! across any number of `packx_solve` calls in the meantime. All other pointers are borrowed for
+//! the duration of the call only.
+
+use alloc::boxed::Box;
+
+use crate::{build_memory, deserialize, solve_with_memory, verify, SolverMemory, SOLUTION_SIZE};
+
+/// Call succeeded (`packx_solve` wrote a solution, `packx_verify` accepted it).
+pub const PACKX_OK: i32 = 0;
+/// `packx_solve` found no solution at the requested difficulty.
+pub const PACKX_NO_SOLUTION: i32 = 1;
+/// `packx_verify` rejected the solution.
+pub const PACKX_INVALID: i32 = 2;
+/// A required pointer argument was null.
+pub const PACKX_NULL_ARG: i32 = -1;
+
+/// Build the solver tables for `pubkey`. Returns null if `pubkey` is null.
+///
+/// # Safety
+/// `pubkey` must be null or point to 32 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn packx_build_memory(pubkey: *const [u8; 32]) -> *mut SolverMemory {
+    if pubkey.is_null() {
+        return core::ptr::null_mut();
+    }
+    Box::into_raw(Box::new(build_memory(&*pubkey)))
+}
+
+/// Solve one 128-byte chunk into `out` (145 serialized bytes).
+///
+/// Returns `PACKX_OK`, `PACKX_NO_SOLUTION` (`out` untouched), or `PACKX_NULL_ARG`.
+///
+/// # Safety
+/// `mem` must be null or a live handle from `packx_build_memory`; `data` must be null or point
+/// to 128 readable bytes; `out` must be null or point to 145 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn packx_solve(
+    mem: *const SolverMemory,
+    data: *const [u8; 128],
+    difficulty: u32,
+    out: *mut [u8; SOLUTION_SIZE],
+) -> i32 {
+    if mem.is_null() || data.is_null() || out.is_null() {
+        return PACKX_NULL_ARG;
+    }
+    match solve_with_memory(&*data, &*mem, difficulty) {
+        Some(solution) => {
+            *out = solution.into();
+            PACKX_OK
+        }
+        None => PACKX_NO_SOLUTION,
+    }
+}
+
+/// Check a 145-byte serialized solution against `pubkey`, `data`, and `difficulty`.
+///
+/// Returns `PACKX_OK`, `PACKX_INVALID`, or `PACKX_NULL_ARG`.
+///
+/// # Safety
+/// Each pointer must be null or point to the stated number of readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn packx_verify(
+    pubkey: *const [u8; 32],
+    data: *const [u8; 128],
+    solution: *const [u8; SOLUTION_SIZE],
+    difficulty: u32,
+) -> i32 {
+    if pubkey.is_null() || data.is_null() || solution.is_null() {
+        return PACKX_NULL_ARG;
+    }
+    let solution = deserialize(&*solution);
+    if verify(&*pubkey, &*data, &solution, difficulty) {
+        PACKX_OK
+    } else {
+        PACKX_INVALID
+    }
+}
+
+/// Release a handle from `packx_build_memory`. Null is a no-op.
+///
+/// # Safety
+/// `mem` must be null or a handle from `packx_build_memory` that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn packx_free_memory(mem: *mut SolverMemory) {
+    if !mem.is_null() {
+        drop(Box::from_raw(mem));
+    }
+}
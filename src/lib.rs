@@ -1,17 +1,128 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use bytemuck::{Pod, Zeroable};
+#[cfg(feature = "alloc")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "std")]
+mod persist;
+#[cfg(feature = "mmap")]
+pub use persist::MappedSolverMemory;
+#[cfg(feature = "tokio")]
+mod async_solve;
+#[cfg(feature = "tokio")]
+pub use async_solve::{solve_async, solve_with_memory_async};
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "cache")]
+pub use cache::MemoryCache;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+pub use stream::{verify_stream, VerifyError};
+#[cfg(feature = "rayon")]
+pub use stream::{verify_stream_parallel, STREAM_BATCH_RECORDS};
+#[cfg(feature = "alloc")]
+mod telemetry;
+#[cfg(feature = "metrics")]
+pub use telemetry::{COMBINATIONS_TRIED, MEMORIES_BUILT, SOLVES_ATTEMPTED, SOLVES_SUCCEEDED};
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::solve_wasm;
+
+mod v2;
+pub use v2::*;
 
-pub const SOLUTION_SIZE: usize = 145; // 1 (bump) + 16 (seeds) + 128 (nonces)
+pub const SOLUTION_SIZE: usize = Solution128::SIZE; // 1 (bump) + 16 (seeds) + 128 (nonces)
 
+/// Bytes in `Solution::to_compact`: the bump and 16 seeds, without nonces.
+pub const SOLUTION_COMPACT_SIZE: usize = 17;
+
+// serialize/deserialize copy the struct bytes directly, so layout drift must not compile.
+const _: () = assert!(SOLUTION_SIZE == 145);
+const _: () = assert!(core::mem::size_of::<Solution>() == SOLUTION_SIZE);
+const _: () = assert!(core::mem::size_of::<Solution64>() == SOLUTION64_SIZE);
+const _: () = assert!(core::mem::size_of::<SolutionG4>() == SOLUTION_G4_SIZE);
+
+/// A packed chunk of `BYTES` bytes in `GROUPS` groups of `GROUP_BYTES = BYTES / GROUPS`,
+/// each group produced by one seed.
+///
+/// The defaults are the 128-byte format in groups of 8 that every concrete API in this crate
+/// uses; other shapes go through the generic `unpack`/`verify` and `solve_with_memory_groups`.
+/// `BYTES` must be a multiple of `GROUPS`, which is checked at compile time wherever the type
+/// is used, and solving needs `GROUP_BYTES <= 8`.
+///
+/// Narrower groups trade size for solvability: a seed must reach fewer targets at once, so
+/// coverage failures get rarer, at one more seed byte per extra group (see `SolutionG4`).
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
-pub struct Solution {
-    pub bump: u8,          // single-byte bump
-    pub seeds: [u8; 16],   // 16 seeds, one per 8-byte group
-    pub nonces: [u8; 128], // 128 nonces (u8), one per byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution<const GROUPS: usize = 16, const BYTES: usize = 128> {
+    pub bump: u8,             // single-byte bump
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub seeds: [u8; GROUPS],  // one seed per `GROUP_BYTES`-byte group
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub nonces: [u8; BYTES],  // one nonce (u8) per byte
 }
 
-impl Solution {
-    pub fn new(bump: u8, seeds: [u8; 16], nonces: [u8; 128]) -> Self {
+/// The standard 128-byte solution; the same type as plain `Solution`.
+pub type Solution128 = Solution<16, 128>;
+
+/// A 64-byte half chunk in 8 groups, serialized in `SOLUTION64_SIZE` bytes, for payloads
+/// too small to justify 128 bytes of solution space.
+///
+/// Not interchangeable with `Solution`: the serialized forms differ in length only, so a
+/// wire protocol mixing both should carry its own format discriminator.
+pub type Solution64 = Solution<8, 64>;
+
+pub const SOLUTION64_SIZE: usize = Solution64::SIZE; // 1 (bump) + 8 (seeds) + 64 (nonces)
+
+/// A 128-byte chunk in 32 groups of 4 bytes, serialized in `SOLUTION_G4_SIZE` bytes.
+///
+/// Each seed only has to reach 4 targets instead of 8, so a group is covered by about 17% of
+/// seeds rather than 3%, and chunks no bump covers practically disappear, for 16 more bytes
+/// than `Solution`. Solved from the same `build_memory` tables; not interchangeable with
+/// `Solution` on the wire.
+pub type SolutionG4 = Solution<32, 128>;
+
+pub const SOLUTION_G4_SIZE: usize = SolutionG4::SIZE; // 1 (bump) + 32 (seeds) + 128 (nonces)
+
+// SAFETY: repr(C) with only u8 and u8-array fields, so there is no padding and any bit
+// pattern is valid, including all zeros.
+unsafe impl<const GROUPS: usize, const BYTES: usize> Zeroable for Solution<GROUPS, BYTES> {}
+unsafe impl<const GROUPS: usize, const BYTES: usize> Pod for Solution<GROUPS, BYTES> {}
+
+impl<const GROUPS: usize, const BYTES: usize> core::hash::Hash for Solution<GROUPS, BYTES> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<const GROUPS: usize, const BYTES: usize> Solution<GROUPS, BYTES> {
+    /// Serialized size: bump, seeds, nonces.
+    pub const SIZE: usize = {
+        assert!(GROUPS > 0 && BYTES.is_multiple_of(GROUPS), "Solution needs BYTES to be a multiple of GROUPS");
+        assert!(core::mem::size_of::<Self>() == 1 + GROUPS + BYTES, "Solution layout has padding");
+        1 + GROUPS + BYTES
+    };
+
+    /// Bytes each seed covers: 8 for the standard format, 4 for `SolutionG4`.
+    pub const GROUP_BYTES: usize = {
+        let _ = Self::SIZE;
+        BYTES / GROUPS
+    };
+
+    pub fn new(bump: u8, seeds: [u8; GROUPS], nonces: [u8; BYTES]) -> Self {
         Solution {
             bump,
             seeds,
@@ -19,12 +130,74 @@ impl Solution {
         }
     }
 
+    /// `new`, rejecting parts that don't unpack to `data` under `pubkey`.
+    ///
+    /// A seed and nonce always hash to some byte, so there is nothing to check without
+    /// the data they are meant to encode; this takes it and reports the first byte whose
+    /// (seed, nonce) pair reconstructs something else. Difficulty is not checked.
+    pub fn new_checked(
+        pubkey: &[u8; 32],
+        data: &[u8; BYTES],
+        bump: u8,
+        seeds: [u8; GROUPS],
+        nonces: [u8; BYTES],
+    ) -> Result<Self, PackxError> {
+        let solution = Self::new(bump, seeds, nonces);
+        match unpack_checked(pubkey, data, &solution) {
+            Ok(_) => Ok(solution),
+            Err(err) => Err(PackxError::WrongByte { index: err.index }),
+        }
+    }
+
+    /// The serialized form, borrowed: bump, seeds, then nonces.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let _ = Self::SIZE;
+        bytemuck::bytes_of(self)
+    }
+
+    /// BLAKE3 of the serialized form, a stable key for deduplicating solutions.
+    ///
+    /// Same bytes as `solution_hash`; named for use as an identifier rather than for difficulty.
+    #[inline]
+    pub fn id(&self) -> [u8; 32] {
+        self.solution_hash()
+    }
+
     /// Leading-zero bits in BLAKE3(serialize(solution)).
     #[inline]
     pub fn difficulty(&self) -> u32 {
-        let bytes = serialize(self);
-        let h = compute_hash(&[&bytes]);
-        get_difficulty(h)
+        get_difficulty(self.solution_hash())
+    }
+
+    /// Full BLAKE3(serialize(solution)), the hash `difficulty` counts zeros in.
+    #[inline]
+    pub fn solution_hash(&self) -> [u8; 32] {
+        compute_hash(&[self.as_bytes()])
+    }
+
+    /// Leading-zero bits in H(serialize(solution)) for the given hash algorithm.
+    #[inline]
+    pub fn difficulty_with(&self, algo: HashAlgo) -> u32 {
+        get_difficulty(self.solution_hash_with(algo))
+    }
+
+    /// Full H(serialize(solution)) for the given hash algorithm.
+    #[inline]
+    pub fn solution_hash_with(&self, algo: HashAlgo) -> [u8; 32] {
+        hash_with(algo, &[self.as_bytes()])
+    }
+
+    /// Reconstruct data using H(pubkey, bump, seed, nonce).
+    pub fn unpack(&self, pubkey: &[u8; 32]) -> [u8; BYTES] {
+        unpack(pubkey, self)
+    }
+}
+
+impl Solution {
+    /// The all-zero solution: bump 0, zero seeds and nonces. Handy as a placeholder.
+    pub const fn zeroed() -> Self {
+        Solution { bump: 0, seeds: [0; 16], nonces: [0; 128] }
     }
 
     /// Serialize to 145 bytes.
@@ -37,13 +210,274 @@ impl Solution {
         deserialize(data)
     }
 
-    /// Reconstruct data using H(pubkey, bump, seed, nonce).
-    pub fn unpack(&self, pubkey: &[u8; 32]) -> [u8; 128] {
-        unpack(pubkey, self)
+    /// Which parts of `other` differ from `self`, for comparing two solutions of the same data.
+    pub fn diff(&self, other: &Solution) -> SolutionDiff {
+        let mut diff = SolutionDiff { bump: self.bump != other.bump, seeds: 0, nonces: 0 };
+        for (g, (a, b)) in self.seeds.iter().zip(&other.seeds).enumerate() {
+            diff.seeds |= ((a != b) as u16) << g;
+        }
+        for (i, (a, b)) in self.nonces.iter().zip(&other.nonces).enumerate() {
+            diff.nonces |= ((a != b) as u128) << i;
+        }
+        diff
+    }
+
+    /// The bump and seeds only, dropping the nonces for `from_compact` to re-derive.
+    pub fn to_compact(&self) -> [u8; SOLUTION_COMPACT_SIZE] {
+        let mut out = [0u8; SOLUTION_COMPACT_SIZE];
+        out[0] = self.bump;
+        out[1..].copy_from_slice(&self.seeds);
+        out
+    }
+
+    /// Rebuild a solution from `to_compact` output by giving each byte of `data` the smallest
+    /// nonce that reaches it, rehashing up to 256 nonces per seed.
+    ///
+    /// That is the nonce `NonceSelection::First` tables (the default, and what `build_memory`
+    /// builds) record, so solutions from those tables round-trip exactly, difficulty included.
+    /// Solutions from `NonceSelection::Random` tables, or edited by hand, come back with
+    /// different nonces and so a different difficulty. The data has to be known to decode,
+    /// so this suits storing proofs of data kept elsewhere, not the data itself.
+    ///
+    /// None if some seed can't reach its byte of `data` at all.
+    #[cfg(feature = "alloc")]
+    pub fn from_compact(pubkey: &[u8; 32], data: &[u8; 128], compact: &[u8; SOLUTION_COMPACT_SIZE]) -> Option<Self> {
+        let mut solution = Solution::zeroed();
+        solution.bump = compact[0];
+        solution.seeds.copy_from_slice(&compact[1..]);
+
+        let mut hasher = BumpHasher::new(pubkey, solution.bump, &HashParams::DEFAULT);
+        let mut present = [0u8; 32];
+        let mut first = [0u8; 256];
+        for (g, &seed) in solution.seeds.iter().enumerate() {
+            fill_row(&mut hasher, seed, &mut present, Some(&mut first));
+            for i in g * 8..g * 8 + 8 {
+                if !bit_test(&present, data[i]) {
+                    return None;
+                }
+                solution.nonces[i] = first[data[i] as usize];
+            }
+        }
+        Some(solution)
+    }
+
+    /// Lowercase hex of the 145-byte form (290 characters).
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut out = String::with_capacity(SOLUTION_SIZE * 2);
+        for &b in self.as_bytes() {
+            out.push(DIGITS[(b >> 4) as usize] as char);
+            out.push(DIGITS[(b & 0xF) as usize] as char);
+        }
+        out
+    }
+
+    /// Parse the 145-byte form from hex, either case.
+    #[cfg(feature = "alloc")]
+    pub fn from_hex(s: &str) -> Result<Self, PackxError> {
+        if s.len() != SOLUTION_SIZE * 2 {
+            return Err(PackxError::BadLength { expected: SOLUTION_SIZE * 2, got: s.len() });
+        }
+        let nibble = |index: usize| -> Result<u8, PackxError> {
+            let c = s.as_bytes()[index];
+            match c {
+                b'0'..=b'9' => Ok(c - b'0'),
+                b'a'..=b'f' => Ok(c - b'a' + 10),
+                b'A'..=b'F' => Ok(c - b'A' + 10),
+                _ => Err(invalid_character(s, index)),
+            }
+        };
+
+        let mut bytes = [0u8; SOLUTION_SIZE];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (nibble(2 * i)? << 4) | nibble(2 * i + 1)?;
+        }
+        Ok(deserialize(&bytes))
+    }
+
+    /// Base58 (Bitcoin alphabet, as used by Solana) of the 145-byte form.
+    #[cfg(feature = "base58")]
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.as_bytes()).into_string()
+    }
+
+    /// Parse the 145-byte form from base58.
+    ///
+    /// A decode failure other than a bad character, which plain decoding doesn't produce today,
+    /// is reported as `BadLength` with `got: 0`.
+    #[cfg(feature = "base58")]
+    pub fn from_base58(s: &str) -> Result<Self, PackxError> {
+        let bytes = bs58::decode(s).into_vec().map_err(|err| match err {
+            bs58::decode::Error::InvalidCharacter { index, .. } | bs58::decode::Error::NonAsciiCharacter { index } => {
+                invalid_character(s, index)
+            }
+            _ => PackxError::BadLength { expected: SOLUTION_SIZE, got: 0 },
+        })?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+/// Differences between two solutions, from `Solution::diff`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolutionDiff {
+    /// The bumps differ. Every byte then hashes differently, so seeds and nonces often do too.
+    pub bump: bool,
+    /// Bit `g` is set when group `g` chose a different seed.
+    pub seeds: u16,
+    /// Bit `i` is set when byte `i` has a different nonce.
+    pub nonces: u128,
+}
+
+impl SolutionDiff {
+    /// The solutions are identical.
+    pub fn is_empty(&self) -> bool {
+        *self == SolutionDiff::default()
+    }
+
+    /// Groups whose seed or any of whose nonces differ, ascending.
+    pub fn groups(&self) -> impl Iterator<Item = usize> {
+        let diff = *self;
+        (0..16).filter(move |&g| diff.seeds >> g & 1 != 0 || (diff.nonces >> (g * 8)) as u8 != 0)
+    }
+}
+
+/// `InvalidCharacter` for the character starting at byte `index` of `s`.
+#[cfg(feature = "alloc")]
+fn invalid_character(s: &str, index: usize) -> PackxError {
+    let character = s.get(index..).and_then(|rest| rest.chars().next()).unwrap_or(char::REPLACEMENT_CHARACTER);
+    PackxError::InvalidCharacter { index, character }
+}
+
+impl Solution64 {
+    /// Serialize to 73 bytes.
+    pub fn to_bytes(&self) -> [u8; SOLUTION64_SIZE] {
+        let mut out = [0u8; SOLUTION64_SIZE];
+        out.copy_from_slice(self.as_bytes());
+        out
+    }
+
+    /// Deserialize from 73 bytes.
+    pub fn from_bytes(data: &[u8; SOLUTION64_SIZE]) -> Self {
+        bytemuck::pod_read_unaligned(data)
+    }
+}
+
+impl SolutionG4 {
+    /// Serialize to 161 bytes.
+    pub fn to_bytes(&self) -> [u8; SOLUTION_G4_SIZE] {
+        let mut out = [0u8; SOLUTION_G4_SIZE];
+        out.copy_from_slice(self.as_bytes());
+        out
+    }
+
+    /// Deserialize from 161 bytes.
+    pub fn from_bytes(data: &[u8; SOLUTION_G4_SIZE]) -> Self {
+        bytemuck::pod_read_unaligned(data)
+    }
+}
+
+impl Default for Solution {
+    fn default() -> Self {
+        Solution::zeroed()
+    }
+}
+
+impl TryFrom<&[u8]> for Solution {
+    type Error = PackxError;
+
+    /// Deserialize from a slice that must be exactly 145 bytes.
+    fn try_from(bytes: &[u8]) -> Result<Self, PackxError> {
+        deserialize_checked(bytes)
+    }
+}
+
+impl From<Solution> for [u8; SOLUTION_SIZE] {
+    fn from(solution: Solution) -> Self {
+        serialize(&solution)
+    }
+}
+
+/// Errors returned by the fallible packx APIs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackxError {
+    /// Input had the wrong number of bytes.
+    BadLength { expected: usize, got: usize },
+    /// No bump has a seed covering every needed byte of this 8-byte group.
+    NoCoverage { group: usize },
+    /// The data was covered but no combination met the requested difficulty.
+    DifficultyUnreached,
+    /// Chunk `index` of a multi-chunk input could not be solved.
+    ChunkUnsolved { index: usize },
+    /// The solve was cancelled through its cancel flag.
+    Cancelled,
+    /// A text encoding had a character outside its alphabet at byte `index`.
+    InvalidCharacter { index: usize, character: char },
+    /// Tables were built for a different pubkey than the one they are used or combined with.
+    PubkeyMismatch,
+    /// Tables being combined were built with different hash algorithms.
+    HashAlgoMismatch,
+    /// Tables being combined both hold `bump`.
+    BumpOverlap { bump: u8 },
+    /// Tables being combined leave `bump` (the first such) without a table.
+    BumpGap { bump: u8 },
+    /// A solution's seed and nonce for byte `index` reconstruct a different byte.
+    WrongByte { index: usize },
+}
+
+impl core::fmt::Display for PackxError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PackxError::BadLength { expected, got } => {
+                write!(f, "bad length: expected {expected} bytes, got {got}")
+            }
+            PackxError::NoCoverage { group } => write!(f, "no seed covers group {group}"),
+            PackxError::DifficultyUnreached => write!(f, "difficulty unreached"),
+            PackxError::ChunkUnsolved { index } => write!(f, "chunk {index} could not be solved"),
+            PackxError::Cancelled => write!(f, "solve cancelled"),
+            PackxError::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {character:?} at byte {index}")
+            }
+            PackxError::PubkeyMismatch => write!(f, "tables were built for a different pubkey"),
+            PackxError::HashAlgoMismatch => write!(f, "tables were built with different hash algorithms"),
+            PackxError::BumpOverlap { bump } => write!(f, "bump {bump} is present in both tables"),
+            PackxError::BumpGap { bump } => write!(f, "no table for bump {bump}"),
+            PackxError::WrongByte { index } => write!(f, "byte {index} does not unpack to the data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PackxError {}
+
+/// First byte where a solution's reconstruction diverged from the expected data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnpackError {
+    /// Group the byte belongs to (`index / GROUP_BYTES`), i.e. which seed produced it.
+    pub group: usize,
+    /// Byte offset within the chunk.
+    pub index: usize,
+    /// Byte in the expected data.
+    pub expected: u8,
+    /// Byte the solution's seed and nonce actually hash to.
+    pub actual: u8,
+}
+
+impl core::fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "byte {} (group {}) unpacked to {:#04x}, expected {:#04x}",
+            self.index, self.group, self.actual, self.expected
+        )
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for UnpackError {}
+
 /// Per-bump table allocated on the heap. Large fields are boxed slices.
+#[cfg(feature = "alloc")]
 #[repr(C)]
 pub struct SeedTable {
     /// [seed][target] -> nonce
@@ -52,31 +486,286 @@ pub struct SeedTable {
     pub present: Box<[[u8; 32]]>,   // len = 256
 }
 
+#[cfg(feature = "alloc")]
+impl SeedTable {
+    /// Borrow the table as a view usable by the solver.
+    #[inline]
+    pub fn view(&self) -> SeedTableRef<'_> {
+        SeedTableRef {
+            nonces: &self.nonces,
+            present: &self.present,
+        }
+    }
+
+    /// Whether some nonce of `seed` hashes to `target`.
+    #[inline]
+    pub fn can_reach(&self, seed: u8, target: u8) -> bool {
+        bit_test(&self.present[seed as usize], target)
+    }
+
+    /// The first nonce of `seed` hashing to `target`, or None if no nonce does. Unlike
+    /// indexing `nonces` directly, a stored 0 is distinguished from an absent target.
+    #[inline]
+    pub fn nonce_for(&self, seed: u8, target: u8) -> Option<u8> {
+        self.can_reach(seed, target).then(|| self.nonces[seed as usize][target as usize])
+    }
+
+    /// Number of reachable targets per seed (popcount of each present bitset).
+    pub fn coverage(&self) -> [u16; 256] {
+        let mut out = [0u16; 256];
+        for (count, present) in out.iter_mut().zip(self.present.iter()) {
+            *count = bit_count(present) as u16;
+        }
+        out
+    }
+
+    /// Total reachable (seed, target) pairs in the table, at most 65536.
+    pub fn coverage_score(&self) -> u32 {
+        self.coverage().iter().map(|&c| c as u32).sum()
+    }
+
+    /// Number of seeds that can reach all 256 targets.
+    pub fn fully_covered_seeds(&self) -> usize {
+        self.present.iter().filter(|present| (0..4).all(|word| bit_word(present, word) == u64::MAX)).count()
+    }
+}
+
+/// Borrowed view of one bump table, backed by a `SeedTable`, mapped file bytes, or any
+/// other storage (it needs no allocator).
+#[derive(Clone, Copy)]
+pub struct SeedTableRef<'a> {
+    /// [seed][target] -> nonce
+    pub nonces: &'a [[u8; 256]],
+    /// [seed] -> 256-bit bitset of achievable targets
+    pub present: &'a [[u8; 32]],
+}
+
+/// Bytes in one serialized `SeedTable`: 256x256 nonces, then 256 32-byte bitsets.
+pub const SEED_TABLE_BYTES: usize = 256 * 256 + 256 * 32;
+
+/// Bytes in a serialized `SolverMemory`: all 256 tables (18 MiB).
+pub const SOLVER_MEMORY_BYTES: usize = 256 * SEED_TABLE_BYTES;
+
+// `SeedTable` rows are cast to and from these byte counts by persistence and `to_bytes`.
+const _: () = assert!(
+    SEED_TABLE_BYTES == core::mem::size_of::<[[u8; 256]; 256]>() + core::mem::size_of::<[[u8; 32]; 256]>()
+);
+
 /// All bumps for one pubkey (heap allocated).
+///
+/// Solving only reads the tables, and the type is `Send + Sync`, so threads can share one
+/// memory by reference or behind an `Arc` (see `solve_shared`); there is no need to clone
+/// the 18 MiB per thread.
+#[cfg(feature = "alloc")]
 pub struct SolverMemory {
     pub tables: Box<[Box<SeedTable>]>,
+    /// Hash the tables were built with; solving uses it for the difficulty check too.
+    pub algo: HashAlgo,
+    /// `SeedTable::coverage_score` of each table, computed once by `SolverMemory::new`.
+    /// Recompute with `new` after editing `tables` in place.
+    pub coverage_scores: Box<[u32]>,
+    /// Pubkey the tables were built for, when known.
+    pubkey: Option<[u8; 32]>,
+}
+
+// Sharing one memory across threads is the intended use; keep it possible.
+#[cfg(feature = "alloc")]
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SolverMemory>();
+    assert_send_sync::<SeedTable>();
+};
+
+// 256-bit sets are stored as [u8; 32] (bit t in bit t & 7 of byte t >> 3) and worked on as
+// four little-endian u64 words, which keeps that byte layout on every target.
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn bit_word(bits: &[u8; 32], word: usize) -> u64 {
+    let mut le = [0u8; 8];
+    le.copy_from_slice(&bits[word * 8..word * 8 + 8]);
+    u64::from_le_bytes(le)
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn set_bit_word(bits: &mut [u8; 32], word: usize, value: u64) {
+    bits[word * 8..word * 8 + 8].copy_from_slice(&value.to_le_bytes());
 }
 
+#[cfg(feature = "alloc")]
 #[inline(always)]
 fn bit_test(bits: &[u8; 32], target: u8) -> bool {
-    let idx = (target >> 3) as usize;
-    let mask = 1u8 << (target & 7);
-    (bits[idx] & mask) != 0
+    (bit_word(bits, (target >> 6) as usize) >> (target & 63)) & 1 != 0
 }
 
+#[cfg(feature = "alloc")]
 #[inline(always)]
 fn bit_set(bits: &mut [u8; 32], target: u8) {
-    let idx = (target >> 3) as usize;
-    let mask = 1u8 << (target & 7);
-    bits[idx] |= mask;
+    let word = (target >> 6) as usize;
+    set_bit_word(bits, word, bit_word(bits, word) | 1u64 << (target & 63));
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn bit_clear(bits: &mut [u8; 32], target: u8) {
+    let word = (target >> 6) as usize;
+    set_bit_word(bits, word, bit_word(bits, word) & !(1u64 << (target & 63)));
+}
+
+/// Number of set bits.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn bit_count(bits: &[u8; 32]) -> u32 {
+    (0..4).map(|word| bit_word(bits, word).count_ones()).sum()
+}
+
+/// Parameters of the per-byte hash H(domain, pubkey, bump, seed, nonce).
+///
+/// Tables must be built and solutions unpacked/verified with the same parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashParams<'a> {
+    offset: usize,
+    algo: HashAlgo,
+    domain: &'a [u8],
+}
+
+impl<'a> HashParams<'a> {
+    /// BLAKE3, target is byte 0 of the hash, empty domain, as used by `build_memory`,
+    /// `unpack`, and `verify`.
+    pub const DEFAULT: HashParams<'a> = HashParams { offset: 0, algo: HashAlgo::Blake3, domain: &[] };
+
+    /// Use byte `offset` (0..32) of the hash output as the target.
+    pub const fn with_offset(self, offset: usize) -> Self {
+        assert!(offset < 32, "hash output offset out of range");
+        HashParams { offset, ..self }
+    }
+
+    /// Hash with `algo` instead of BLAKE3.
+    pub const fn with_algo(self, algo: HashAlgo) -> Self {
+        HashParams { algo, ..self }
+    }
+
+    /// Prefix every per-byte hash input with `domain`, so deployments using different
+    /// domains get independent tables and solutions for the same pubkey. An empty domain
+    /// hashes exactly as before.
+    pub const fn with_domain(self, domain: &'a [u8]) -> Self {
+        HashParams { domain, ..self }
+    }
+
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub const fn algo(&self) -> HashAlgo {
+        self.algo
+    }
+
+    pub const fn domain(&self) -> &'a [u8] {
+        self.domain
+    }
+
+    /// Target byte reached by (pubkey, bump, seed, nonce).
+    #[inline(always)]
+    pub fn h0(&self, pubkey: &[u8; 32], bump: u8, seed: u8, nonce: u8) -> u8 {
+        let bump_b = [bump];
+        let seed_b = [seed];
+        let nonce_b = [nonce];
+        hash_with(self.algo, &[self.domain, pubkey, &bump_b, &seed_b, &nonce_b])[self.offset]
+    }
+}
+
+impl Default for HashParams<'_> {
+    fn default() -> Self {
+        HashParams::DEFAULT
+    }
+}
+
+/// What the difficulty hash covers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DifficultyMode {
+    /// H(serialize(solution)), as used by `verify` and `solve_with_memory`.
+    #[default]
+    SolutionOnly,
+    /// H(pubkey || data || serialize(solution)), binding the difficulty to the packed data.
+    DataBound,
+}
+
+impl DifficultyMode {
+    /// The difficulty hash of `solution` for `pubkey` and `data` under this mode.
+    pub fn hash(&self, pubkey: &[u8; 32], data: &[u8; 128], solution: &Solution) -> [u8; 32] {
+        self.hash_with(HashAlgo::Blake3, pubkey, data, solution)
+    }
+
+    /// Leading zero bits of `hash`.
+    pub fn difficulty(&self, pubkey: &[u8; 32], data: &[u8; 128], solution: &Solution) -> u32 {
+        get_difficulty(self.hash(pubkey, data, solution))
+    }
+
+    fn hash_with(&self, algo: HashAlgo, pubkey: &[u8; 32], data: &[u8; 128], solution: &Solution) -> [u8; 32] {
+        match self {
+            DifficultyMode::SolutionOnly => solution.solution_hash_with(algo),
+            DifficultyMode::DataBound => hash_with(algo, &[pubkey, data, solution.as_bytes()]),
+        }
+    }
+}
+
+/// Hash function used for both the per-byte hash and the difficulty hash.
+///
+/// Tables, solutions, and verification must all use the same algorithm: a solution found
+/// under one hash does not verify under another.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashAlgo {
+    /// BLAKE3, routed through Solana's `hashv` when the `solana` feature is on.
+    #[default]
+    Blake3,
+    /// SHA-256 via the `sha2` crate.
+    #[cfg(feature = "sha256")]
+    Sha256,
 }
 
 #[inline(always)]
-fn h0(pubkey: &[u8; 32], bump: u8, seed: u8, nonce: u8) -> u8 {
-    let bump_b = [bump];
-    let seed_b = [seed];
-    let nonce_b = [nonce];
-    compute_hash(&[pubkey, &bump_b, &seed_b, &nonce_b])[0]
+fn hash_with(algo: HashAlgo, inputs: &[&[u8]]) -> [u8; 32] {
+    match algo {
+        HashAlgo::Blake3 => compute_hash(inputs),
+        #[cfg(feature = "sha256")]
+        HashAlgo::Sha256 => {
+            use sha2::Digest;
+            let mut hasher = sha2::Sha256::new();
+            for input in inputs {
+                hasher.update(input);
+            }
+            hasher.finalize().into()
+        }
+    }
+}
+
+/// `HashParams::h0` for a fixed pubkey and bump, with the input laid out once.
+///
+/// Hashing one contiguous 35-byte buffer beats feeding four slices per call, and beats
+/// cloning a prefix-seeded `blake3::Hasher` (the clone copies its whole chaining-value stack).
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+struct BumpHasher<'a> {
+    input: [u8; 35],
+    params: HashParams<'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> BumpHasher<'a> {
+    fn new(pubkey: &[u8; 32], bump: u8, params: &HashParams<'a>) -> Self {
+        let mut input = [0u8; 35];
+        input[..32].copy_from_slice(pubkey);
+        input[32] = bump;
+        BumpHasher { input, params: *params }
+    }
+
+    #[inline(always)]
+    fn h0(&mut self, seed: u8, nonce: u8) -> u8 {
+        self.input[33] = seed;
+        self.input[34] = nonce;
+        hash_with(self.params.algo, &[self.params.domain, &self.input])[self.params.offset]
+    }
 }
 
 #[inline(always)]
@@ -95,10 +784,24 @@ fn compute_hash(inputs: &[&[u8]]) -> [u8; 32] {
     }
 }
 
+/// Highest possible difficulty: every bit of the 32-byte hash is zero.
+pub const MAX_DIFFICULTY: u32 = 256;
+
+/// Leading zero bits of `hash`, MSB-first within each byte. Ranges 0..=MAX_DIFFICULTY.
 #[inline]
 fn get_difficulty(hash: [u8; 32]) -> u32 {
+    leading_zero_bits(&hash)
+}
+
+/// Leading zero bits of `bytes`, the same count packx uses for difficulty, for hashes of
+/// any length.
+///
+/// Bytes are read in order and bits MSB-first within each byte, so `[0x00, 0x1F]` has 11.
+/// Ranges from 0 to `8 * bytes.len()`; an empty slice has 0.
+#[inline]
+pub fn leading_zero_bits(bytes: &[u8]) -> u32 {
     let mut count = 0u32;
-    for &b in &hash {
+    for &b in bytes {
         let lz = b.leading_zeros();
         count += lz;
         if lz < 8 {
@@ -108,6 +811,37 @@ fn get_difficulty(hash: [u8; 32]) -> u32 {
     count
 }
 
+/// Expected number of combinations hashed to reach `difficulty`: each try has a 2^-difficulty
+/// chance of enough leading zero bits. Difficulties above `MAX_DIFFICULTY` are clamped to it.
+pub fn expected_iterations(difficulty: u32) -> f64 {
+    // 2^d exactly, built from the exponent bits since `powi` needs std.
+    f64::from_bits((1023 + difficulty.min(MAX_DIFFICULTY) as u64) << 52)
+}
+
+/// `expected_iterations(difficulty)` at `iters_per_sec`, saturating at `Duration::MAX` when the
+/// estimate doesn't fit (or the rate isn't positive).
+pub fn estimated_solve_time(difficulty: u32, iters_per_sec: f64) -> core::time::Duration {
+    if iters_per_sec.is_nan() || iters_per_sec <= 0.0 {
+        return core::time::Duration::MAX;
+    }
+    let secs = expected_iterations(difficulty) / iters_per_sec;
+    core::time::Duration::try_from_secs_f64(secs).unwrap_or(core::time::Duration::MAX)
+}
+
+/// Whether `hash`, read as a 256-bit big-endian number, is at most `target`.
+///
+/// Lets protocols express difficulty as an explicit threshold rather than a zero-bit count.
+#[inline]
+pub fn meets_target(hash: &[u8; 32], target: &[u8; 32]) -> bool {
+    hash <= target
+}
+
+/// Leading-zero bits in BLAKE3 of an already-serialized solution.
+#[inline]
+pub fn difficulty_from_bytes(bytes: &[u8; SOLUTION_SIZE]) -> u32 {
+    get_difficulty(compute_hash(&[bytes]))
+}
+
 #[inline]
 pub fn serialize(solution: &Solution) -> [u8; SOLUTION_SIZE] {
     let mut out = [0u8; SOLUTION_SIZE];
@@ -126,74 +860,602 @@ pub fn deserialize(bytes_in: &[u8; SOLUTION_SIZE]) -> Solution {
     s
 }
 
-/// Build one bump table on the heap. No large stack locals.
-pub fn build_one_bump(pubkey: &[u8; 32], bump: u8) -> Box<SeedTable> {
-    // Use boxed slices so the large storage is on the heap.
-    let mut table = Box::new(SeedTable {
+/// `deserialize` for untrusted input such as network data: any slice is accepted and a
+/// length other than `SOLUTION_SIZE` is a `BadLength` error rather than a panic.
+///
+/// `Solution` has alignment 1, so the slice may start at any offset of a larger buffer.
+pub fn deserialize_checked(bytes: &[u8]) -> Result<Solution, PackxError> {
+    let array: &[u8; SOLUTION_SIZE] = bytes.try_into().map_err(|_| PackxError::BadLength {
+        expected: SOLUTION_SIZE,
+        got: bytes.len(),
+    })?;
+    Ok(deserialize(array))
+}
+
+/// Zeroed bump table. Boxed slices keep the large storage on the heap.
+#[cfg(feature = "alloc")]
+fn alloc_table() -> Box<SeedTable> {
+    Box::new(SeedTable {
         nonces: vec![[0u8; 256]; 256].into_boxed_slice(),
         present: vec![[0u8; 32]; 256].into_boxed_slice(),
-    });
+    })
+}
 
-    for seed in 0u8..=u8::MAX {
-        let present_row: &mut [u8; 32] = &mut table.present[seed as usize];
-        let nonces_row: &mut [u8; 256] = &mut table.nonces[seed as usize];
+/// Build one bump table on the heap. No large stack locals.
+#[cfg(feature = "alloc")]
+pub fn build_one_bump(pubkey: &[u8; 32], bump: u8) -> Box<SeedTable> {
+    build_one_bump_with_params(pubkey, bump, &HashParams::DEFAULT)
+}
 
-        for nonce in 0u8..=u8::MAX {
-            let t = h0(pubkey, bump, seed, nonce);
-            if !bit_test(present_row, t) {
-                bit_set(present_row, t);
-                nonces_row[t as usize] = nonce;
+/// Build one bump table using custom hash parameters.
+#[cfg(feature = "alloc")]
+pub fn build_one_bump_with_params(pubkey: &[u8; 32], bump: u8, params: &HashParams) -> Box<SeedTable> {
+    let mut table = alloc_table();
+    fill_one_bump(pubkey, bump, params, &mut table);
+    table
+}
+
+/// Which nonce a table records when several nonces of a seed reach the same target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonceSelection {
+    /// The smallest nonce, as `build_one_bump` records.
+    #[default]
+    First,
+    /// A uniformly random one, so packings aren't biased toward small nonces. The choice for a
+    /// (seed, target) pair depends only on the pubkey, bump, seed, target, and this value, so
+    /// any row can be rebuilt on its own. Every nonce is hashed.
+    Random(u64),
+}
+
+/// `build_one_bump`, choosing among nonces that reach the same target by `selection`.
+///
+/// The present bitsets don't depend on the selection, and any selection reconstructs
+/// correctly with `unpack` and `verify`.
+#[cfg(feature = "alloc")]
+pub fn build_one_bump_with_selection(pubkey: &[u8; 32], bump: u8, selection: NonceSelection) -> Box<SeedTable> {
+    let mut table = alloc_table();
+    match selection {
+        NonceSelection::First => fill_one_bump(pubkey, bump, &HashParams::DEFAULT, &mut table),
+        NonceSelection::Random(seed) => {
+            let mut hasher = BumpHasher::new(pubkey, bump, &HashParams::DEFAULT);
+            let key = selection_key(pubkey, bump, seed);
+            let SeedTable { nonces, present } = &mut *table;
+            for (seed, (nonces_row, present_row)) in nonces.iter_mut().zip(present.iter_mut()).enumerate() {
+                fill_row_random(&mut hasher, seed as u8, present_row, nonces_row, key);
             }
         }
     }
-
     table
 }
 
-/// Build all 256 bump tables on the heap.
-pub fn build_memory(pubkey: &[u8; 32]) -> SolverMemory {
-    let mut vec_tables: Vec<Box<SeedTable>> = Vec::with_capacity(256);
-    for bump in 0u8..=u8::MAX {
-        vec_tables.push(build_one_bump(pubkey, bump));
-    }
-    SolverMemory {
-        tables: vec_tables.into_boxed_slice(),
-    }
+/// `build_memory` with `build_one_bump_with_selection` tables.
+#[cfg(feature = "alloc")]
+pub fn build_memory_with_selection(pubkey: &[u8; 32], selection: NonceSelection) -> SolverMemory {
+    let tables = (0u8..=u8::MAX).map(|bump| build_one_bump_with_selection(pubkey, bump, selection));
+    let mem = SolverMemory::new(tables.collect(), HashAlgo::Blake3).with_pubkey(pubkey);
+    telemetry::memory_built();
+    mem
 }
 
-/// Seed that can cover a group, with the 8 nonces to use.
-#[derive(Clone, Copy)]
-struct SeedCandidate {
+/// Key for `NonceSelection::Random(user_seed)` draws in one bump.
+#[cfg(feature = "alloc")]
+fn selection_key(pubkey: &[u8; 32], bump: u8, user_seed: u64) -> u64 {
+    let digest = compute_hash(&[pubkey, &[bump], &user_seed.to_le_bytes()]);
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+/// Like `fill_row`, but keep a uniformly random nonce per target by reservoir sampling:
+/// the k-th nonce reaching a target replaces the kept one with probability 1/k. Draw k for
+/// `target` is derived from (`key`, seed, target, k) alone.
+#[cfg(feature = "alloc")]
+fn fill_row_random(
+    hasher: &mut BumpHasher<'_>,
     seed: u8,
-    nonces8: [u8; 8],
+    present_row: &mut [u8; 32],
+    nonces_row: &mut [u8; 256],
+    key: u64,
+) {
+    *present_row = [0; 32];
+    *nonces_row = [0; 256];
+    let mut seen = [0u16; 256];
+    for nonce in 0u8..=u8::MAX {
+        let t = hasher.h0(seed, nonce);
+        seen[t as usize] += 1;
+        if seen[t as usize] == 1 {
+            bit_set(present_row, t);
+            nonces_row[t as usize] = nonce;
+        } else {
+            let mut state = key ^ ((seed as u64) << 32 | (t as u64) << 16 | seen[t as usize] as u64);
+            if splitmix64(&mut state).is_multiple_of(seen[t as usize] as u64) {
+                nonces_row[t as usize] = nonce;
+            }
+        }
+    }
 }
 
-/// Build candidates for group g using table.
-fn build_group_candidates(data: &[u8; 128], g: usize, table: &SeedTable) -> Vec<SeedCandidate> {
-    let cs = g * 8;
-    let need = [
-        data[cs + 0], data[cs + 1], data[cs + 2], data[cs + 3],
-        data[cs + 4], data[cs + 5], data[cs + 6], data[cs + 7],
-    ];
+/// Build one bump table with its 256 seed rows hashed in parallel on rayon.
+///
+/// Matches `build_one_bump`. Use it when only a few bumps are needed (a partial memory or a
+/// single bump); for a full memory, building whole bumps in parallel splits the work more
+/// coarsely and keeps every thread on its own table, so prefer parallelism across bumps there.
+#[cfg(feature = "rayon")]
+pub fn build_one_bump_parallel(pubkey: &[u8; 32], bump: u8) -> Box<SeedTable> {
+    use rayon::prelude::*;
+
+    let mut table = alloc_table();
+    let SeedTable { nonces, present } = &mut *table;
+    nonces.par_iter_mut().zip(present.par_iter_mut()).enumerate().for_each_with(
+        BumpHasher::new(pubkey, bump, &HashParams::DEFAULT),
+        |hasher, (seed, (nonces_row, present_row))| fill_row(hasher, seed as u8, present_row, Some(nonces_row)),
+    );
+    table
+}
 
-    let mut out = Vec::with_capacity(8);
+/// Build one bump into an existing table, overwriting every row regardless of prior contents.
+#[cfg(feature = "alloc")]
+fn fill_one_bump(pubkey: &[u8; 32], bump: u8, params: &HashParams, table: &mut SeedTable) {
+    let mut hasher = BumpHasher::new(pubkey, bump, params);
 
     for seed in 0u8..=u8::MAX {
-        let present = &table.present[seed as usize];
-        if !(bit_test(present, need[0]) &&
-             bit_test(present, need[1]) &&
-             bit_test(present, need[2]) &&
-             bit_test(present, need[3]) &&
-             bit_test(present, need[4]) &&
-             bit_test(present, need[5]) &&
-             bit_test(present, need[6]) &&
-             bit_test(present, need[7])) {
-            continue;
-        }
+        let nonces_row: &mut [u8; 256] = &mut table.nonces[seed as usize];
+        *nonces_row = [0; 256];
+        fill_row(&mut hasher, seed, &mut table.present[seed as usize], Some(nonces_row));
+    }
+}
 
-        let row = &table.nonces[seed as usize];
-        out.push(SeedCandidate {
-            seed,
+/// Hash every nonce of `seed` into `present_row`, recording the first nonce reaching each
+/// target in `nonces_row` when one is given.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn fill_row(hasher: &mut BumpHasher<'_>, seed: u8, present_row: &mut [u8; 32], mut nonces_row: Option<&mut [u8; 256]>) {
+    *present_row = [0; 32];
+
+    // Once every target is present, later nonces can't change the row.
+    let mut covered = 0usize;
+    for nonce in 0u8..=u8::MAX {
+        let t = hasher.h0(seed, nonce);
+        if !bit_test(present_row, t) {
+            bit_set(present_row, t);
+            if let Some(row) = nonces_row.as_deref_mut() {
+                row[t as usize] = nonce;
+            }
+            covered += 1;
+            if covered == 256 {
+                break;
+            }
+        }
+    }
+}
+
+/// Build all 256 bump tables on the heap.
+#[cfg(feature = "alloc")]
+pub fn build_memory(pubkey: &[u8; 32]) -> SolverMemory {
+    build_memory_with_params(pubkey, &HashParams::DEFAULT)
+}
+
+/// Build all 256 bump tables using custom hash parameters.
+#[cfg(feature = "alloc")]
+pub fn build_memory_with_params(pubkey: &[u8; 32], params: &HashParams) -> SolverMemory {
+    let mut vec_tables: Vec<Box<SeedTable>> = Vec::with_capacity(256);
+    for bump in 0u8..=u8::MAX {
+        vec_tables.push(build_one_bump_with_params(pubkey, bump, params));
+    }
+    telemetry::memory_built();
+    SolverMemory::new(vec_tables.into_boxed_slice(), params.algo()).with_pubkey(pubkey)
+}
+
+/// `build_memory`, calling `on_progress(bump)` after each bump's table is built, in order
+/// 0..=255, for progress reporting.
+#[cfg(feature = "alloc")]
+pub fn build_memory_with_progress(pubkey: &[u8; 32], mut on_progress: impl FnMut(u8)) -> SolverMemory {
+    let tables = (0u8..=u8::MAX).map(|bump| {
+        let table = build_one_bump(pubkey, bump);
+        on_progress(bump);
+        table
+    });
+    let mem = SolverMemory::new(tables.collect(), HashAlgo::Blake3).with_pubkey(pubkey);
+    telemetry::memory_built();
+    mem
+}
+
+/// Rebuild `mem` for `pubkey` in place, reusing its allocation across a batch of pubkeys.
+///
+/// Every byte is overwritten, so the result matches `build_memory(pubkey)` whatever `mem`
+/// held before. A memory without exactly 256 tables is reallocated.
+#[cfg(feature = "alloc")]
+pub fn build_memory_into(pubkey: &[u8; 32], mem: &mut SolverMemory) {
+    if mem.tables.len() != 256 {
+        mem.tables = (0..256).map(|_| alloc_table()).collect();
+    }
+    for (bump, table) in mem.tables.iter_mut().enumerate() {
+        fill_one_bump(pubkey, bump as u8, &HashParams::DEFAULT, table);
+    }
+    mem.algo = HashAlgo::Blake3;
+    mem.coverage_scores = mem.tables.iter().map(|table| table.coverage_score()).collect();
+    mem.pubkey = Some(*pubkey);
+    telemetry::memory_built();
+}
+
+#[cfg(feature = "alloc")]
+impl SolverMemory {
+    /// Wrap prebuilt tables, scoring each bump's coverage once up front.
+    ///
+    /// The result doesn't know its pubkey; record it with `with_pubkey`.
+    pub fn new(tables: Box<[Box<SeedTable>]>, algo: HashAlgo) -> Self {
+        let coverage_scores = tables.iter().map(|table| table.coverage_score()).collect();
+        SolverMemory { tables, algo, coverage_scores, pubkey: None }
+    }
+
+    /// Record the pubkey the tables were built for, so pubkey-checked solves and saves can
+    /// catch tables used with the wrong pubkey. The tables themselves are not checked.
+    pub fn with_pubkey(mut self, pubkey: &[u8; 32]) -> Self {
+        self.pubkey = Some(*pubkey);
+        self
+    }
+
+    /// The pubkey the tables were built for: set by every `build_memory*` function, by
+    /// `load_from_path` and `PartialSolverMemory::merge`, and by `with_pubkey`. None for
+    /// memories assembled from raw tables or bytes.
+    pub fn pubkey(&self) -> Option<&[u8; 32]> {
+        self.pubkey.as_ref()
+    }
+
+    /// Bumps from best to worst coverage score; equal scores keep ascending bump order.
+    pub fn bumps_by_coverage(&self) -> Vec<u8> {
+        let mut bumps: Vec<u8> = (0..self.tables.len()).map(|bump| bump as u8).collect();
+        bumps.sort_by_key(|&bump| core::cmp::Reverse(self.coverage_scores[bump as usize]));
+        bumps
+    }
+
+    /// Worst-covered seed across all tables as `(bump, seed, reachable targets)`.
+    ///
+    /// Ties go to the lowest bump, then the lowest seed.
+    pub fn min_coverage(&self) -> (u8, u8, u16) {
+        let mut worst = (0u8, 0u8, u16::MAX);
+        for (bump, table) in self.tables.iter().enumerate() {
+            for (seed, &count) in table.coverage().iter().enumerate() {
+                if count < worst.2 {
+                    worst = (bump as u8, seed as u8, count);
+                }
+            }
+        }
+        worst
+    }
+
+    /// Serialize every table in bump order, each as its nonces followed by its present
+    /// bitsets: the layout `save_to_path` writes after its header.
+    ///
+    /// Each table's two arrays are separate allocations, so there is no single borrowed byte
+    /// view of the memory; this copies into one `SOLVER_MEMORY_BYTES` buffer.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.tables.len() * SEED_TABLE_BYTES);
+        for table in self.tables.iter() {
+            out.extend_from_slice(bytemuck::cast_slice(&table.nonces));
+            out.extend_from_slice(bytemuck::cast_slice(&table.present));
+        }
+        out
+    }
+
+    /// Rebuild tables written by `to_bytes`. The bytes don't record a hash algorithm, so the
+    /// result uses BLAKE3; set `algo` afterwards for tables built otherwise.
+    ///
+    /// Fails with `BadLength` unless `bytes` is exactly `SOLVER_MEMORY_BYTES` long. Any
+    /// contents of the right length are accepted: nonces and bitsets are plain `u8` arrays
+    /// (`Pod`), so bytemuck can copy them in without validation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SolverMemory, PackxError> {
+        if bytes.len() != SOLVER_MEMORY_BYTES {
+            return Err(PackxError::BadLength { expected: SOLVER_MEMORY_BYTES, got: bytes.len() });
+        }
+        let tables = bytes.chunks_exact(SEED_TABLE_BYTES).map(|chunk| {
+            let (nonces, present) = chunk.split_at(256 * 256);
+            let mut table = alloc_table();
+            bytemuck::cast_slice_mut(&mut table.nonces).copy_from_slice(nonces);
+            bytemuck::cast_slice_mut(&mut table.present).copy_from_slice(present);
+            table
+        });
+        Ok(SolverMemory::new(tables.collect(), HashAlgo::Blake3))
+    }
+
+    /// Copy out just the present bitsets, for keeping feasibility checks around after the
+    /// full tables are dropped.
+    pub fn coverage_memory(&self) -> CoverageMemory {
+        let tables = self.tables.iter().map(|table| Box::new(CoverageTable { present: table.present.clone() }));
+        CoverageMemory { tables: tables.collect() }
+    }
+
+    /// Read one byte per `PREFAULT_STRIDE` of every table so their pages are resident before
+    /// latency-sensitive solving.
+    ///
+    /// Building or loading writes every byte, so fresh tables are already resident; this
+    /// matters when they may have been swapped out since, and for `MappedSolverMemory`,
+    /// whose pages are faulted in lazily (see its `prefault`).
+    pub fn prefault(&self) {
+        for table in self.tables.iter() {
+            touch_pages(bytemuck::cast_slice(&table.nonces));
+            touch_pages(bytemuck::cast_slice(&table.present));
+        }
+    }
+
+    /// Every `(bump, target)` that no seed of the bump reaches, in bump then target order.
+    ///
+    /// Data containing such a target can never be packed with that bump. With 256 seeds each
+    /// reaching about 162 targets a gap is vanishingly unlikely, so the list is normally
+    /// empty. Uses only the present bitsets.
+    pub fn coverage_gaps(&self) -> Vec<(u8, u8)> {
+        gaps_in(self.tables.iter().map(|table| &table.present[..]))
+    }
+}
+
+/// Bytes between the reads `SolverMemory::prefault` makes: the common 4 KiB page size. On
+/// systems with larger pages some reads are redundant, which costs little.
+pub const PREFAULT_STRIDE: usize = 4096;
+
+/// Read one byte per `PREFAULT_STRIDE` of `bytes`, plus the last, so the reads can't be
+/// optimized away.
+#[cfg(feature = "alloc")]
+fn touch_pages(bytes: &[u8]) {
+    let mut acc = bytes.last().copied().unwrap_or(0);
+    for page in bytes.chunks(PREFAULT_STRIDE) {
+        acc ^= page[0];
+    }
+    core::hint::black_box(acc);
+}
+
+/// The present bitsets of one bump table without its nonces: about 8 KiB instead of 72 KiB.
+///
+/// Enough to tell whether data can be packed, not to build a solution.
+#[cfg(feature = "alloc")]
+pub struct CoverageTable {
+    /// [seed] -> 256-bit bitset of achievable targets
+    pub present: Box<[[u8; 32]]>, // len = 256
+}
+
+/// Coverage of all bumps for one pubkey, the feasibility-only counterpart of `SolverMemory`.
+#[cfg(feature = "alloc")]
+pub struct CoverageMemory {
+    pub tables: Box<[Box<CoverageTable>]>,
+}
+
+/// Build the present bitsets for one bump, hashing exactly as `build_one_bump` does.
+#[cfg(feature = "alloc")]
+pub fn build_coverage(pubkey: &[u8; 32], bump: u8) -> Box<CoverageTable> {
+    let mut table = Box::new(CoverageTable { present: vec![[0u8; 32]; 256].into_boxed_slice() });
+    let mut hasher = BumpHasher::new(pubkey, bump, &HashParams::DEFAULT);
+    for seed in 0u8..=u8::MAX {
+        fill_row(&mut hasher, seed, &mut table.present[seed as usize], None);
+    }
+    table
+}
+
+/// Build coverage for all 256 bumps (about 2 MiB).
+#[cfg(feature = "alloc")]
+pub fn build_coverage_memory(pubkey: &[u8; 32]) -> CoverageMemory {
+    CoverageMemory { tables: (0u8..=u8::MAX).map(|bump| build_coverage(pubkey, bump)).collect() }
+}
+
+#[cfg(feature = "alloc")]
+impl CoverageMemory {
+    /// Whether some bump has a covering seed for every group of `data`, i.e. whether
+    /// `solve_with_memory` can find a solution at difficulty 0.
+    pub fn can_pack(&self, data: &[u8; 128]) -> bool {
+        any_bump_covers(self.tables.iter().map(|table| &table.present[..]), data)
+    }
+
+    /// Indices of the zero-padded 128-byte chunks of `data` that can't be packed at all.
+    pub fn unpackable_chunks(&self, data: &[u8]) -> Vec<usize> {
+        let chunks = data.chunks(128).map(pad_chunk);
+        chunks.enumerate().filter(|(_, chunk)| !self.can_pack(chunk)).map(|(i, _)| i).collect()
+    }
+
+    /// Same as `SolverMemory::coverage_gaps`.
+    pub fn coverage_gaps(&self) -> Vec<(u8, u8)> {
+        gaps_in(self.tables.iter().map(|table| &table.present[..]))
+    }
+
+    /// Same as `unreachable_targets`, from coverage alone.
+    pub fn unreachable_targets(&self, data: &[u8; 128]) -> Option<Vec<(usize, u8)>> {
+        unreachable_in(self.tables.iter().map(|table| &table.present[..]), data)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SeedTable {
+    fn zeroize(&mut self) {
+        bytemuck::cast_slice_mut::<[u8; 256], u8>(&mut self.nonces).zeroize();
+        bytemuck::cast_slice_mut::<[u8; 32], u8>(&mut self.present).zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SolverMemory {
+    fn zeroize(&mut self) {
+        for table in self.tables.iter_mut() {
+            table.zeroize();
+        }
+        self.coverage_scores.zeroize();
+    }
+}
+
+/// With the `zeroize` feature, tables are wiped with volatile writes before being freed.
+/// That touches all 18 MiB again, so dropping costs roughly as much as a large memset.
+#[cfg(feature = "zeroize")]
+impl Drop for SolverMemory {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// Tables for a contiguous range of bumps only, e.g. canonical PDA bumps near 255.
+#[cfg(feature = "alloc")]
+pub struct PartialSolverMemory {
+    pubkey: [u8; 32],
+    bumps: core::ops::RangeInclusive<u8>,
+    /// One table per bump in `bumps`, in order.
+    pub tables: Box<[Box<SeedTable>]>,
+    /// Hash the tables were built with; solving uses it for the difficulty check too.
+    pub algo: HashAlgo,
+}
+
+#[cfg(feature = "alloc")]
+impl PartialSolverMemory {
+    /// The pubkey the tables were built for.
+    pub fn pubkey(&self) -> &[u8; 32] {
+        &self.pubkey
+    }
+
+    /// The bumps this memory holds tables for.
+    pub fn bumps(&self) -> core::ops::RangeInclusive<u8> {
+        self.bumps.clone()
+    }
+
+    /// Combine two memories built separately, e.g. bumps 0..=127 and 128..=255 on different
+    /// machines, into a full `SolverMemory`.
+    ///
+    /// Fails unless both were built for the same pubkey and hash algorithm and their ranges
+    /// are disjoint and together cover every bump.
+    pub fn merge(self, other: PartialSolverMemory) -> Result<SolverMemory, PackxError> {
+        if self.pubkey != other.pubkey {
+            return Err(PackxError::PubkeyMismatch);
+        }
+        if self.algo != other.algo {
+            return Err(PackxError::HashAlgoMismatch);
+        }
+
+        let (pubkey, algo) = (self.pubkey, self.algo);
+        let mut slots: Vec<Option<Box<SeedTable>>> = (0..256).map(|_| None).collect();
+        for part in [self, other] {
+            for (bump, table) in part.bumps.zip(part.tables.into_vec()) {
+                if slots[bump as usize].replace(table).is_some() {
+                    return Err(PackxError::BumpOverlap { bump });
+                }
+            }
+        }
+        let tables = slots
+            .into_iter()
+            .enumerate()
+            .map(|(bump, table)| table.ok_or(PackxError::BumpGap { bump: bump as u8 }))
+            .collect::<Result<_, _>>()?;
+        Ok(SolverMemory::new(tables, algo).with_pubkey(&pubkey))
+    }
+
+    /// Table for `bump`, or None if it's outside the built range.
+    pub fn table(&self, bump: u8) -> Option<&SeedTable> {
+        if !self.bumps.contains(&bump) {
+            return None;
+        }
+        Some(&self.tables[(bump - self.bumps.start()) as usize])
+    }
+}
+
+/// Build tables only for `bumps`, cutting build time and RAM when the rest will never be searched.
+#[cfg(feature = "alloc")]
+pub fn build_memory_range(pubkey: &[u8; 32], bumps: core::ops::RangeInclusive<u8>) -> PartialSolverMemory {
+    let tables: Vec<Box<SeedTable>> = bumps.clone().map(|bump| build_one_bump(pubkey, bump)).collect();
+    telemetry::memory_built();
+    PartialSolverMemory {
+        pubkey: *pubkey,
+        bumps,
+        tables: tables.into_boxed_slice(),
+        algo: HashAlgo::Blake3,
+    }
+}
+
+/// Seed that can cover a group, with the 8 nonces to use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeedCandidate {
+    pub seed: u8,
+    /// Nonce for each of the group's 8 bytes, in order.
+    pub nonces8: [u8; 8],
+}
+
+/// The data bytes group g (of `width` bytes) must reconstruct, as 8 targets. Narrower groups
+/// repeat their bytes to fill the 8, which asks exactly the same of a seed.
+#[inline(always)]
+fn group_need<const BYTES: usize>(data: &[u8; BYTES], g: usize, width: usize) -> [u8; 8] {
+    let cs = g * width;
+    core::array::from_fn(|k| data[cs + k % width])
+}
+
+/// Whether a seed's present bitset reaches all 8 needed targets.
+///
+/// Branchless fold over the 8 bits; measurably faster than short-circuiting `bit_test`s
+/// since the branches are unpredictable on random data.
+#[cfg(not(feature = "simd"))]
+#[inline(always)]
+fn covers_all(present: &[u8; 32], need: &[u8; 8]) -> bool {
+    let mut acc = 1u8;
+    for &t in need {
+        acc &= present[(t >> 3) as usize] >> (t & 7);
+    }
+    acc & 1 != 0
+}
+
+/// Whether a seed's present bitset reaches all 8 needed targets, as one 8-lane gather.
+///
+/// Slower than the scalar fold on x86-64 (benches/coverage.rs, `covers_all` group), so the
+/// `simd` feature is for targets with a cheap gather.
+#[cfg(feature = "simd")]
+#[inline(always)]
+fn covers_all(present: &[u8; 32], need: &[u8; 8]) -> bool {
+    use core::simd::prelude::*;
+
+    let targets = u8x8::from_array(*need);
+    let idx: Simd<usize, 8> = (targets >> u8x8::splat(3)).cast();
+    let bytes = u8x8::gather_or_default(present, idx);
+    let bits = (bytes >> (targets & u8x8::splat(7))) & u8x8::splat(1);
+    bits.simd_eq(u8x8::splat(1)).all()
+}
+
+/// `covers_all` over only the targets whose bit is set in `care` (bit k for `need[k]`).
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn covers_masked(present: &[u8; 32], need: &[u8; 8], care: u8) -> bool {
+    let mut acc = 1u8;
+    for (k, &t) in need.iter().enumerate() {
+        acc &= (present[(t >> 3) as usize] >> (t & 7)) | !(care >> k);
+    }
+    acc & 1 != 0
+}
+
+/// Cheap check that some seed covers group g, using only the present bitsets.
+#[cfg(feature = "alloc")]
+fn group_has_coverage<const BYTES: usize>(data: &[u8; BYTES], g: usize, width: usize, table: SeedTableRef<'_>) -> bool {
+    present_covers_group(table.present, data, g, width)
+}
+
+#[cfg(feature = "alloc")]
+fn present_covers_group<const BYTES: usize>(present: &[[u8; 32]], data: &[u8; BYTES], g: usize, width: usize) -> bool {
+    let need = group_need(data, g, width);
+    present.iter().any(|row| covers_all(row, &need))
+}
+
+/// Build candidates for group g using table into `out`, reusing its allocation.
+#[cfg(feature = "alloc")]
+fn fill_group_candidates<const BYTES: usize>(
+    data: &[u8; BYTES],
+    g: usize,
+    width: usize,
+    table: SeedTableRef<'_>,
+    out: &mut Vec<SeedCandidate>,
+) {
+    out.clear();
+    out.extend(covering_candidates(data, g, width, table));
+}
+
+/// Seeds covering group g, in ascending seed order, with their nonces gathered.
+#[inline(always)]
+fn covering_candidates<'a, const BYTES: usize>(
+    data: &[u8; BYTES],
+    g: usize,
+    width: usize,
+    table: SeedTableRef<'a>,
+) -> impl Iterator<Item = SeedCandidate> + 'a {
+    let need = group_need(data, g, width);
+
+    (0u8..=u8::MAX).filter(move |&seed| covers_all(&table.present[seed as usize], &need)).map(move |seed| {
+        let row = &table.nonces[seed as usize];
+        SeedCandidate {
+            seed,
             nonces8: [
                 row[need[0] as usize],
                 row[need[1] as usize],
@@ -204,36 +1466,158 @@ fn build_group_candidates(data: &[u8; 128], g: usize, table: &SeedTable) -> Vec<
                 row[need[6] as usize],
                 row[need[7] as usize],
             ],
-        });
+        }
+    })
+}
+
+/// Every seed of `table` that covers group `g` (bytes `g*8..g*8+8`) of `data`, in ascending
+/// seed order, for building custom solvers on the precomputed tables.
+///
+/// Panics if `g >= 16`.
+#[cfg(feature = "alloc")]
+pub fn group_candidates(data: &[u8; 128], g: usize, table: &SeedTable) -> Vec<SeedCandidate> {
+    let mut out = Vec::new();
+    fill_group_candidates(data, g, 8, table.view(), &mut out);
+    out
+}
+
+/// How reachable each byte of one group is, from `group_candidate_report`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupReport {
+    /// The group's 8 bytes of data, the targets its seed must reach.
+    pub targets: [u8; 8],
+    /// How many seeds reach each target on its own.
+    pub reach: [u16; 8],
+    /// How many seeds reach all 8 targets: the size of the group's candidate list.
+    pub covering: u16,
+}
+
+impl GroupReport {
+    /// Position (0..8) of the least reachable target, the first on ties: the byte most to
+    /// blame when `covering` is 0 or small.
+    pub fn bottleneck(&self) -> usize {
+        (0..8).min_by_key(|&k| self.reach[k]).unwrap()
+    }
+}
+
+/// Per-target seed counts for group `g` of `data` in `table`, for seeing which byte keeps a
+/// group's candidate list short or empty. Panics if `g >= 16`.
+#[cfg(feature = "alloc")]
+pub fn group_candidate_report(data: &[u8; 128], g: usize, table: &SeedTable) -> GroupReport {
+    let targets = group_need(data, g, 8);
+    let mut report = GroupReport { targets, reach: [0; 8], covering: 0 };
+    for row in table.present.iter() {
+        for (count, &t) in report.reach.iter_mut().zip(&targets) {
+            *count += bit_test(row, t) as u16;
+        }
+        report.covering += covers_all(row, &targets) as u16;
     }
+    report
+}
 
+/// The seeds of `table` covering group `g` of `data`, as a 256-bit set indexed like the
+/// present bitsets (bit `s & 7` of byte `s >> 3` for seed `s`).
+///
+/// Same seeds as `group_candidates` without gathering nonces, so constraint solvers can
+/// intersect or count candidates across groups cheaply. Panics if `g >= 16`.
+#[cfg(feature = "alloc")]
+pub fn covering_seeds(data: &[u8; 128], g: usize, table: &SeedTable) -> [u8; 32] {
+    let need = group_need(data, g, 8);
+    let mut out = [0u8; 32];
+    for (byte, rows) in out.iter_mut().zip(table.present.chunks_exact(8)) {
+        for (bit, row) in rows.iter().enumerate() {
+            *byte |= (covers_all(row, &need) as u8) << bit;
+        }
+    }
     out
 }
 
-/// Iterator over the cartesian product of candidate lists.
-struct MixedRadix {
-    radices: [usize; 16],
-    idx: [usize; 16],
+/// The targets set in a present bitset, ascending: the bytes a seed can reach. Works on any
+/// 256-bit set in the same layout, such as `covering_seeds`.
+///
+/// Copies the 32 bytes and allocates nothing.
+pub fn present_targets(bits: &[u8; 32]) -> impl Iterator<Item = u8> {
+    let bits = *bits;
+    (0..32).flat_map(move |i| {
+        let mut rest = bits[i];
+        core::iter::from_fn(move || {
+            if rest == 0 {
+                return None;
+            }
+            let bit = rest.trailing_zeros() as usize;
+            rest &= rest - 1;
+            Some((i * 8 + bit) as u8)
+        })
+    })
+}
+
+/// Mixed-radix odometer: every index tuple `idx` with `idx[i] < radices[i]`, i.e. the
+/// cartesian product of `N` lists by position. The solvers walk candidate lists with it.
+///
+/// The all-zero tuple comes first, then position 0 increments fastest, carrying into
+/// position 1 when it wraps, and so on. Tuple number `k` in this order has digits
+/// `k % radices[0]`, `k / radices[0] % radices[1]`, ..., which `seek` relies on.
+///
+/// ```
+/// use packx::MixedRadixCounter;
+///
+/// let tuples: Vec<[usize; 2]> = MixedRadixCounter::new([2, 3]).unwrap().collect();
+/// assert_eq!(tuples, [[0, 0], [1, 0], [0, 1], [1, 1], [0, 2], [1, 2]]);
+///
+/// let mut counter = MixedRadixCounter::new([2, 3]).unwrap();
+/// assert_eq!(counter.total(), 6);
+/// counter.seek(3);
+/// assert_eq!(counter.next(), Some([1, 1]));
+///
+/// // An empty list empties the product.
+/// assert!(MixedRadixCounter::new([2, 0]).is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MixedRadixCounter<const N: usize = 16> {
+    radices: [usize; N],
+    idx: [usize; N],
     first: bool,
     done: bool,
 }
 
-impl MixedRadix {
-    fn new(radices: [usize; 16]) -> Option<Self> {
-        if radices.iter().any(|&r| r == 0) {
+impl<const N: usize> MixedRadixCounter<N> {
+    /// A counter starting at the all-zero tuple, or None if some radix is 0 (nothing to
+    /// yield). With `N == 0` the single empty tuple is yielded once.
+    pub fn new(radices: [usize; N]) -> Option<Self> {
+        if radices.contains(&0) {
             return None;
         }
         Some(Self {
             radices,
-            idx: [0; 16],
+            idx: [0; N],
             first: true,
             done: false,
         })
     }
+
+    /// Number of tuples, saturating at `u128::MAX` (256^16 is one past it).
+    pub fn total(&self) -> u128 {
+        self.radices.iter().fold(1u128, |acc, &r| acc.saturating_mul(r as u128))
+    }
+
+    /// Position the counter so the next item is tuple number `index` of the order above.
+    /// Seeking past the end exhausts it; when `total` saturated, every `index` is in range.
+    pub fn seek(&mut self, mut index: u128) {
+        if index >= self.total() && self.total() != u128::MAX {
+            self.done = true;
+            return;
+        }
+        for (digit, &radix) in self.idx.iter_mut().zip(&self.radices) {
+            *digit = (index % radix as u128) as usize;
+            index /= radix as u128;
+        }
+        self.first = true;
+        self.done = false;
+    }
 }
 
-impl Iterator for MixedRadix {
-    type Item = [usize; 16];
+impl<const N: usize> Iterator for MixedRadixCounter<N> {
+    type Item = [usize; N];
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
             return None;
@@ -242,7 +1626,7 @@ impl Iterator for MixedRadix {
             self.first = false;
             return Some(self.idx);
         }
-        for pos in 0..16 {
+        for pos in 0..N {
             self.idx[pos] += 1;
             if self.idx[pos] < self.radices[pos] {
                 return Some(self.idx);
@@ -256,170 +1640,3877 @@ impl Iterator for MixedRadix {
 }
 
 /// Solve for one bump using its table by scanning per-group candidates and trying combinations.
+#[cfg(feature = "alloc")]
 pub fn solve_one_bump(
     data: &[u8; 128],
     bump: u8,
     table: &SeedTable,
     difficulty: u32,
 ) -> Option<Solution> {
-    let mut cands: [Vec<SeedCandidate>; 16] = core::array::from_fn(|_| Vec::new());
-    for g in 0..16 {
-        cands[g] = build_group_candidates(data, g, table);
-        if cands[g].is_empty() {
-            return None;
-        }
+    let found = search_one_bump(data, bump, table, difficulty, HashAlgo::Blake3, None);
+    telemetry::solve_finished(found.is_some());
+    found
+}
+
+/// Solve for one bump whose table was built with `algo`, checking difficulty under the same hash.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_with_algo(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    algo: HashAlgo,
+) -> Option<Solution> {
+    search_one_bump(data, bump, table, difficulty, algo, None)
+}
+
+/// Number of seed combinations one bump offers for `data`, saturating at `u128::MAX`; 0 if
+/// some group has no covering seed. An upper bound on what `solve_one_bump` can try.
+#[cfg(feature = "alloc")]
+pub fn combination_count(data: &[u8; 128], table: &SeedTable) -> u128 {
+    let Ok(search) = BumpSearch::<16, 128>::new(data, 0, table.view()) else { return 0 };
+    search.combinations().map_or(0, |iter| iter.total())
+}
+
+/// Caps on how much work a solve may do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolveLimits {
+    /// Most combinations tried per bump before moving on; `usize::MAX` is unbounded.
+    pub max_iterations: usize,
+}
+
+impl SolveLimits {
+    /// No cap, matching `solve_one_bump` and `solve_with_memory`.
+    pub const UNLIMITED: SolveLimits = SolveLimits { max_iterations: usize::MAX };
+
+    pub const fn max_iterations(max_iterations: usize) -> Self {
+        SolveLimits { max_iterations }
     }
+}
 
-    let mut order: [usize; 16] = core::array::from_fn(|i| i);
-    order.sort_by_key(|&g| cands[g].len());
+impl Default for SolveLimits {
+    fn default() -> Self {
+        SolveLimits::UNLIMITED
+    }
+}
 
-    let radices_ordered: [usize; 16] = core::array::from_fn(|i| cands[order[i]].len());
-    let Some(iter) = MixedRadix::new(radices_ordered) else { return None; };
+/// Solve for one bump, trying at most `limits.max_iterations` combinations before returning None.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_limited(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    limits: &SolveLimits,
+) -> Option<Solution> {
+    BumpSearch::new(data, bump, table.view()).ok()?.find(difficulty, HashAlgo::Blake3, limits, None)
+}
 
-    for idxs_ordered in iter {
-        let mut seeds_out = [0u8; 16];
-        let mut nonces_out = [0u8; 128];
+/// Scratch entries `solve_one_bump_nostd` needs: room for all 256 seeds in each of 16 groups.
+pub const NOSTD_SCRATCH_LEN: usize = 16 * 256;
 
-        for (pos, &g) in order.iter().enumerate() {
-            let choice = cands[g][idxs_ordered[pos]];
-            seeds_out[g] = choice.seed;
-            let cs = g * 8;
-            nonces_out[cs..cs + 8].copy_from_slice(&choice.nonces8);
+/// `solve_one_bump` without allocating, for `no_std` targets without `alloc`.
+///
+/// Candidates are gathered into `scratch`, a caller-provided buffer of at least
+/// `NOSTD_SCRATCH_LEN` entries (about 36 KiB, which may be too large for a small stack;
+/// a `static` buffer works). Combinations are tried in the same order as `solve_one_bump`,
+/// so both return the same solution.
+///
+/// Panics if `scratch` is shorter than `NOSTD_SCRATCH_LEN`.
+pub fn solve_one_bump_nostd(
+    data: &[u8; 128],
+    bump: u8,
+    table: SeedTableRef<'_>,
+    difficulty: u32,
+    scratch: &mut [SeedCandidate],
+) -> Option<Solution> {
+    assert!(scratch.len() >= NOSTD_SCRATCH_LEN, "scratch needs NOSTD_SCRATCH_LEN candidates");
+    let mut lens = [0usize; 16];
+    for (g, (slots, len)) in scratch.chunks_exact_mut(256).zip(lens.iter_mut()).enumerate() {
+        for (slot, cand) in slots.iter_mut().zip(covering_candidates(data, g, 8, table)) {
+            *slot = cand;
+            *len += 1;
         }
+    }
 
-        let solution = Solution { bump, seeds: seeds_out, nonces: nonces_out };
+    // Keys are unique, so the unstable sort matches `BumpSearch::fill`'s order.
+    let mut order: [usize; 16] = core::array::from_fn(|i| i);
+    order.sort_unstable_by_key(|&g| (lens[g], g));
+    let radices: [usize; 16] = core::array::from_fn(|pos| lens[order[pos]]);
+
+    for idxs in MixedRadixCounter::new(radices)? {
+        let mut solution = Solution::zeroed();
+        solution.bump = bump;
+        for (pos, &g) in order.iter().enumerate() {
+            let choice = scratch[g * 256 + idxs[pos]];
+            solution.seeds[g] = choice.seed;
+            solution.nonces[g * 8..g * 8 + 8].copy_from_slice(&choice.nonces8);
+        }
         if solution.difficulty() >= difficulty {
             return Some(solution);
         }
     }
-
     None
 }
 
-/// Solve using a precomputed all-bumps table.
-pub fn solve_with_memory(
+/// How a per-bump solve orders groups in its odometer. Position 0 turns fastest.
+///
+/// Every combination is one independent hash, so no order reaches a difficulty in fewer
+/// tries on average; they differ only in which solution is found first. `benches/ordering.rs`
+/// found no consistent winner at difficulties 0 through 12 (all within about 15%, leader
+/// changing between difficulties), so the default stays `FewestFirst`, the order every other
+/// solver in the crate uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OrderingStrategy {
+    /// Groups with the fewest candidates first, ties by group index.
+    #[default]
+    FewestFirst,
+    /// Groups with the most candidates first, ties by group index.
+    MostFirst,
+    /// Group index order, ignoring candidate counts.
+    GroupIndex,
+}
+
+/// `solve_one_bump` with the odometer's group order chosen by `ordering`.
+///
+/// `OrderingStrategy::FewestFirst` returns the same solution as `solve_one_bump`.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_ordered(
     data: &[u8; 128],
-    mem: &SolverMemory,
+    bump: u8,
+    table: &SeedTable,
     difficulty: u32,
+    ordering: OrderingStrategy,
 ) -> Option<Solution> {
-    for bump in 0u8..=u8::MAX {
-        let table: &SeedTable = &mem.tables[bump as usize];
-        if let Some(solution) = solve_one_bump(data, bump, table, difficulty) {
-            return Some(solution);
+    let mut search: BumpSearch = BumpSearch::new(data, bump, table.view()).ok()?;
+    search.reorder(ordering);
+    search.find(difficulty, HashAlgo::Blake3, &SolveLimits::UNLIMITED, None)
+}
+
+/// Solve for seeds alone with every nonce fixed in advance: each group needs a seed whose
+/// hashes with the group's 8 given nonces are exactly its 8 bytes of `data`.
+///
+/// A random seed matches a whole group with probability 2^-64, so this only succeeds for
+/// data laid out from known seeds and nonces; it needs no tables and at most 256 seeds per
+/// group, most rejected after one hash. When several seeds match a group their
+/// combinations are searched for `difficulty` as usual. None if some group has no
+/// matching seed or no combination reaches `difficulty`.
+#[cfg(feature = "alloc")]
+pub fn solve_with_fixed_nonces(
+    data: &[u8; 128],
+    pubkey: &[u8; 32],
+    bump: u8,
+    nonces: [u8; 128],
+    difficulty: u32,
+) -> Option<Solution> {
+    let mut hasher = BumpHasher::new(pubkey, bump, &HashParams::DEFAULT);
+    let mut search: BumpSearch = BumpSearch::empty();
+    search.bump = bump;
+    for (g, cands) in search.cands.iter_mut().enumerate() {
+        let nonces8: [u8; 8] = nonces[g * 8..g * 8 + 8].try_into().unwrap();
+        let need = group_need(data, g, 8);
+        let mut matches = |seed: u8| (0..8).all(|k| hasher.h0(seed, nonces8[k]) == need[k]);
+        cands.extend((0..=u8::MAX).filter(|&seed| matches(seed)).map(|seed| SeedCandidate { seed, nonces8 }));
+        if cands.is_empty() {
+            return None;
         }
     }
-    None
+    search.reorder(OrderingStrategy::FewestFirst);
+    search.find(difficulty, HashAlgo::Blake3, &SolveLimits::UNLIMITED, None)
 }
 
-/// Solve by first building the precompute for this pubkey, then searching.
-pub fn solve(
-    pubkey: &[u8; 32],
+/// Restrictions on which seed combinations a solve may return.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveConstraints {
+    /// Require every group to use a different seed.
+    pub distinct_seeds: bool,
+}
+
+impl SolveConstraints {
+    /// No restrictions, matching `solve_one_bump`.
+    pub const NONE: SolveConstraints = SolveConstraints { distinct_seeds: false };
+}
+
+/// `solve_one_bump`, only returning solutions that satisfy `constraints`.
+///
+/// With `distinct_seeds`, combinations are visited in the same order as `solve_one_bump`,
+/// but a partial assignment that repeats a seed is dropped along with everything under it.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_constrained(
     data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
     difficulty: u32,
+    constraints: &SolveConstraints,
 ) -> Option<Solution> {
-    let mem = build_memory(pubkey);
-    solve_with_memory(data, &mem, difficulty)
+    BumpSearch::new(data, bump, table.view()).ok()?.find_constrained(
+        difficulty,
+        HashAlgo::Blake3,
+        &SolveLimits::UNLIMITED,
+        constraints,
+    )
 }
 
-/// Reconstruct data using H(pubkey, bump, seed, nonce).
-pub fn unpack(pubkey: &[u8; 32], solution: &Solution) -> [u8; 128] {
-    let mut data = [0u8; 128];
-    for g in 0..16 {
-        let seed = solution.seeds[g];
-        let cs = g * 8;
-        for i in 0..8 {
-            let nonce = solution.nonces[cs + i];
-            data[cs + i] = h0(pubkey, solution.bump, seed, nonce);
+/// `solve_with_memory`, only returning solutions that satisfy `constraints`.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_constrained(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    constraints: &SolveConstraints,
+) -> Option<Solution> {
+    let mut search = BumpSearch::empty();
+    ALL_BUMPS.into_iter().find_map(|bump| {
+        search.fill(data, bump, mem.tables[bump as usize].view()).ok()?;
+        search.find_constrained(difficulty, mem.algo, &SolveLimits::UNLIMITED, constraints)
+    })
+}
+
+/// Solve for one bump, visiting each group's candidates in an order shuffled by `seed`.
+///
+/// Every combination is still reachable, so this finds a solution exactly when
+/// `solve_one_bump` does. The difficulty hash covers the whole serialized solution, so no
+/// candidate order is faster on average (see the `heuristic` bench); the point is that
+/// workers given different seeds explore the same bump in decorrelated orders.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_heuristic(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    seed: u64,
+) -> Option<Solution> {
+    let mut search = BumpSearch::new(data, bump, table.view()).ok()?;
+    search.shuffle(seed);
+    search.find(difficulty, HashAlgo::Blake3, &SolveLimits::UNLIMITED, None)
+}
+
+/// SplitMix64 step; a tiny deterministic generator for shuffling candidate lists.
+#[cfg(feature = "alloc")]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Reusable candidate storage, so repeated solves don't allocate 16 lists per bump.
+#[cfg(feature = "alloc")]
+pub struct SolveScratch {
+    search: BumpSearch,
+}
+
+#[cfg(feature = "alloc")]
+impl SolveScratch {
+    pub fn new() -> Self {
+        SolveScratch {
+            search: BumpSearch::empty(),
         }
     }
-    data
 }
 
-/// Check reconstruction and difficulty.
-pub fn verify(pubkey: &[u8; 32], data: &[u8; 128], solution: &Solution, difficulty: u32) -> bool {
-    if unpack(pubkey, solution) != *data {
-        return false;
+#[cfg(feature = "alloc")]
+impl Default for SolveScratch {
+    fn default() -> Self {
+        Self::new()
     }
-    solution.difficulty() >= difficulty
 }
 
-#[cfg(test)]
+/// Same as `solve_one_bump`, but fills candidate lists into `scratch` instead of allocating.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_with_scratch(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    scratch: &mut SolveScratch,
+) -> Option<Solution> {
+    scratch.search.fill(data, bump, table.view()).ok()?;
+    scratch.search.find(difficulty, HashAlgo::Blake3, &SolveLimits::UNLIMITED, None)
+}
+
+/// Number of combinations tried between checks of a cancel flag. Each combination costs
+/// one hash, so a cancelled solve stops within a few thousand hashes.
+pub const CANCEL_POLL_INTERVAL: usize = 4096;
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
+/// Per-group candidate lists for one bump, with groups ordered for the odometer.
+#[cfg(feature = "alloc")]
+struct BumpSearch<const GROUPS: usize = 16, const BYTES: usize = 128> {
+    bump: u8,
+    cands: [Vec<SeedCandidate>; GROUPS],
+    /// Search position -> group index, fewest candidates first (`OrderingStrategy::FewestFirst`)
+    /// unless reordered.
+    order: [usize; GROUPS],
+}
+
+#[cfg(feature = "alloc")]
+impl<const GROUPS: usize, const BYTES: usize> BumpSearch<GROUPS, BYTES> {
+    /// Bytes per group. Candidates carry 8 nonces, so groups can't be wider.
+    const WIDTH: usize = {
+        assert!(Solution::<GROUPS, BYTES>::GROUP_BYTES <= 8, "solving needs groups of at most 8 bytes");
+        Solution::<GROUPS, BYTES>::GROUP_BYTES
+    };
+
+    fn empty() -> Self {
+        Self {
+            bump: 0,
+            cands: core::array::from_fn(|_| Vec::new()),
+            order: core::array::from_fn(|i| i),
+        }
+    }
+
+    /// Build candidates for every group; Err(group) for the first group with no covering seed.
+    fn new(data: &[u8; BYTES], bump: u8, table: SeedTableRef<'_>) -> Result<Self, usize> {
+        let mut search = Self::empty();
+        search.fill(data, bump, table)?;
+        Ok(search)
+    }
+
+    /// Refill the candidate lists in place for another bump or chunk, keeping their allocations.
+    ///
+    /// Coverage of every group is checked first, so bumps that can't cover the data
+    /// bail out before any nonce arrays are gathered.
+    fn fill(&mut self, data: &[u8; BYTES], bump: u8, table: SeedTableRef<'_>) -> Result<(), usize> {
+        let _ = Solution::<GROUPS, BYTES>::SIZE;
+        if let Some(g) = (0..GROUPS).find(|&g| !group_has_coverage(data, g, Self::WIDTH, table)) {
+            return Err(g);
+        }
+
+        self.bump = bump;
+        for (g, cand) in self.cands.iter_mut().enumerate() {
+            fill_group_candidates(data, g, Self::WIDTH, table, cand);
+        }
+
+        self.reorder(OrderingStrategy::FewestFirst);
+        Ok(())
+    }
+
+    /// `fill`, but only bytes set in `mask` need reconstructing; the rest take nonce 0.
+    /// `mask[g]` holds group `g`'s bits, so this assumes 8-byte groups.
+    fn fill_masked(&mut self, data: &[u8; BYTES], mask: &[u8], bump: u8, table: SeedTableRef<'_>) -> Result<(), usize> {
+        let _ = Solution::<GROUPS, BYTES>::SIZE;
+        let covers = |g: usize, seed: usize| covers_masked(&table.present[seed], &group_need(data, g, 8), mask[g]);
+        if let Some(g) = (0..GROUPS).find(|&g| !(0..256).any(|seed| covers(g, seed))) {
+            return Err(g);
+        }
+
+        self.bump = bump;
+        for (g, cands) in self.cands.iter_mut().enumerate() {
+            let need = group_need(data, g, 8);
+            cands.clear();
+            cands.extend((0..256).filter(|&seed| covers(g, seed)).map(|seed| {
+                let row = &table.nonces[seed];
+                SeedCandidate {
+                    seed: seed as u8,
+                    nonces8: core::array::from_fn(|k| if mask[g] >> k & 1 != 0 { row[need[k] as usize] } else { 0 }),
+                }
+            }));
+        }
+        self.reorder(OrderingStrategy::FewestFirst);
+        Ok(())
+    }
+
+    /// Reset the search order. Ties break on group index so it depends only on the inputs.
+    fn reorder(&mut self, ordering: OrderingStrategy) {
+        self.order = core::array::from_fn(|i| i);
+        let cands = &self.cands;
+        match ordering {
+            OrderingStrategy::FewestFirst => self.order.sort_by_key(|&g| (cands[g].len(), g)),
+            OrderingStrategy::MostFirst => self.order.sort_by_key(|&g| (core::cmp::Reverse(cands[g].len()), g)),
+            OrderingStrategy::GroupIndex => {}
+        }
+    }
+
+    /// Fisher-Yates shuffle of every group's candidates, deterministic in `seed`.
+    fn shuffle(&mut self, seed: u64) {
+        let mut state = seed;
+        for cands in self.cands.iter_mut() {
+            for i in (1..cands.len()).rev() {
+                let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+                cands.swap(i, j);
+            }
+        }
+    }
+
+    /// First combination meeting `difficulty` under `algo`, trying at most `limits.max_iterations`.
+    /// Returns None early once `cancel` is set, polling every `CANCEL_POLL_INTERVAL` combinations.
+    fn find(
+        &self,
+        difficulty: u32,
+        algo: HashAlgo,
+        limits: &SolveLimits,
+        cancel: Option<&AtomicBool>,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        self.find_by(limits, cancel, |solution| solution.difficulty_with(algo) >= difficulty)
+    }
+
+    /// `find`, skipping combinations that violate `constraints`.
+    fn find_constrained(
+        &self,
+        difficulty: u32,
+        algo: HashAlgo,
+        limits: &SolveLimits,
+        constraints: &SolveConstraints,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        let accept = |solution: &Solution<GROUPS, BYTES>| solution.difficulty_with(algo) >= difficulty;
+        if constraints.distinct_seeds {
+            self.find_distinct_by(limits, None, accept)
+        } else {
+            self.find_by(limits, None, accept)
+        }
+    }
+
+    /// `find_by` over combinations whose seeds are all different.
+    fn find_distinct_by(
+        &self,
+        limits: &SolveLimits,
+        cancel: Option<&AtomicBool>,
+        mut accept: impl FnMut(&Solution<GROUPS, BYTES>) -> bool,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        let mut idxs = [0usize; GROUPS];
+        let mut used = [0u8; 32];
+        let mut budget = limits.max_iterations;
+        self.distinct_below(GROUPS, &mut idxs, &mut used, &mut budget, cancel, &mut accept)
+    }
+
+    /// Depth-first over search positions `..pos`, outermost first so the visiting order matches
+    /// the odometer. A candidate whose seed is in `used` is skipped with its whole subtree.
+    /// `budget` counts down per complete combination; 0 unwinds the search.
+    fn distinct_below(
+        &self,
+        pos: usize,
+        idxs: &mut [usize; GROUPS],
+        used: &mut [u8; 32],
+        budget: &mut usize,
+        cancel: Option<&AtomicBool>,
+        accept: &mut impl FnMut(&Solution<GROUPS, BYTES>) -> bool,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        if pos == 0 {
+            if *budget == 0 {
+                return None;
+            }
+            *budget -= 1;
+            if budget.is_multiple_of(CANCEL_POLL_INTERVAL) && is_cancelled(cancel) {
+                *budget = 0;
+                return None;
+            }
+            let solution = self.solution(idxs);
+            return accept(&solution).then_some(solution);
+        }
+
+        let pos = pos - 1;
+        for (i, cand) in self.cands[self.order[pos]].iter().enumerate() {
+            if *budget == 0 {
+                return None;
+            }
+            if bit_test(used, cand.seed) {
+                continue;
+            }
+            bit_set(used, cand.seed);
+            idxs[pos] = i;
+            let found = self.distinct_below(pos, idxs, used, budget, cancel, accept);
+            bit_clear(used, cand.seed);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// First combination accepted by `accept`, under the same limits and cancel polling as `find`.
+    fn find_by(
+        &self,
+        limits: &SolveLimits,
+        cancel: Option<&AtomicBool>,
+        accept: impl FnMut(&Solution<GROUPS, BYTES>) -> bool,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        self.find_range_by(0, limits.max_iterations as u128, cancel, accept)
+    }
+
+    /// First accepted combination among the `len` starting at linear index `start`.
+    ///
+    /// Each combination is reassembled and hashed from scratch. The hash is nearly all of the
+    /// cost (benches/incremental.rs: ~205 ns of ~230 ns per combination), but it can't be made
+    /// incremental: every group's seed sits in bytes 1..17, inside the first 64-byte BLAKE3
+    /// block, so no prefix stays stable between neighbours. Even a free first block would only
+    /// save ~45 ns, and moving the seeds would change the wire format and every difficulty.
+    fn find_range_by(
+        &self,
+        start: u128,
+        len: u128,
+        cancel: Option<&AtomicBool>,
+        mut accept: impl FnMut(&Solution<GROUPS, BYTES>) -> bool,
+    ) -> Option<Solution<GROUPS, BYTES>> {
+        let mut tried = 0u64;
+        let mut found = None;
+        for (i, idxs_ordered) in (0..len).zip(self.combinations_from(start)?) {
+            if i % CANCEL_POLL_INTERVAL as u128 == 0 && is_cancelled(cancel) {
+                break;
+            }
+
+            tried += 1;
+            let solution = self.solution(&idxs_ordered);
+            if accept(&solution) {
+                found = Some(solution);
+                break;
+            }
+        }
+
+        telemetry::combinations_tried(tried);
+        found
+    }
+
+    /// Odometer over candidate indices, in search order.
+    fn combinations(&self) -> Option<MixedRadixCounter<GROUPS>> {
+        self.combinations_from(0)
+    }
+
+    /// Odometer starting at linear combination `start` of the sequential order.
+    fn combinations_from(&self, start: u128) -> Option<MixedRadixCounter<GROUPS>> {
+        let radices_ordered: [usize; GROUPS] = core::array::from_fn(|i| self.cands[self.order[i]].len());
+        let mut iter = MixedRadixCounter::new(radices_ordered)?;
+        if start > 0 {
+            iter.seek(start);
+        }
+        Some(iter)
+    }
+
+    /// Assemble the solution picked by one odometer position.
+    fn solution(&self, idxs_ordered: &[usize; GROUPS]) -> Solution<GROUPS, BYTES> {
+        let mut seeds_out = [0u8; GROUPS];
+        let mut nonces_out = [0u8; BYTES];
+
+        for (pos, &g) in self.order.iter().enumerate() {
+            let choice = self.cands[g][idxs_ordered[pos]];
+            seeds_out[g] = choice.seed;
+            let cs = g * Self::WIDTH;
+            nonces_out[cs..cs + Self::WIDTH].copy_from_slice(&choice.nonces8[..Self::WIDTH]);
+        }
+
+        Solution { bump: self.bump, seeds: seeds_out, nonces: nonces_out }
+    }
+}
+
+/// Core per-bump search. Returns None early once `cancel` is set.
+#[cfg(feature = "alloc")]
+fn search_one_bump(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    algo: HashAlgo,
+    cancel: Option<&AtomicBool>,
+) -> Option<Solution> {
+    BumpSearch::new(data, bump, table.view()).ok()?.find(difficulty, algo, &SolveLimits::UNLIMITED, cancel)
+}
+
+/// Solve using a precomputed all-bumps table.
+///
+/// Deterministic: the same pubkey, data, and difficulty always yield the same solution.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+) -> Option<Solution> {
+    let found = try_solve_with_memory(data, mem, difficulty).ok();
+    telemetry::solve_finished(found.is_some());
+    found
+}
+
+/// `solve_with_memory` on a memory shared between threads, for workers that each own an
+/// `Arc` clone. Sharing by `&SolverMemory` works just as well where lifetimes allow.
+#[cfg(feature = "alloc")]
+pub fn solve_shared(data: &[u8; 128], mem: alloc::sync::Arc<SolverMemory>, difficulty: u32) -> Option<Solution> {
+    solve_with_memory(data, &mem, difficulty)
+}
+
+/// `try_solve_with_memory` for chunks of `BYTES` bytes in `GROUPS` groups, e.g. 64-byte chunks
+/// with `solve_with_memory_groups::<8, 64>`. The tables don't depend on the chunk size.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_groups<const GROUPS: usize, const BYTES: usize>(
+    data: &[u8; BYTES],
+    mem: &SolverMemory,
+    difficulty: u32,
+) -> Result<Solution<GROUPS, BYTES>, PackxError> {
+    try_solve_in(&mut BumpSearch::empty(), data, mem, ALL_BUMPS, difficulty, &SolveLimits::UNLIMITED, None)
+}
+
+/// Every bump, in the order `solve_with_memory` searches them.
+pub const ALL_BUMPS: core::ops::RangeInclusive<u8> = 0..=u8::MAX;
+
+/// Solve using a precomputed all-bumps table, searching only `bumps`, in the given order.
+///
+/// Useful when a downstream program only accepts some bumps. Passing `0..=255` (`ALL_BUMPS`)
+/// reproduces `solve_with_memory`.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_bumps(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    bumps: impl IntoIterator<Item = u8>,
+) -> Option<Solution> {
+    try_solve_in(&mut BumpSearch::empty(), data, mem, bumps, difficulty, &SolveLimits::UNLIMITED, None).ok()
+}
+
+/// Work done by `solve_with_memory_stats`.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Bumps visited, including ones skipped for lack of coverage.
+    pub bumps_tried: usize,
+    /// Combinations hashed across all bumps.
+    pub combinations_tried: u64,
+    /// Wall time of the whole solve.
+    pub elapsed: std::time::Duration,
+}
+
+/// `solve_with_memory`, also reporting how much work the solve took.
+#[cfg(feature = "std")]
+pub fn solve_with_memory_stats(data: &[u8; 128], mem: &SolverMemory, difficulty: u32) -> Option<(Solution, SolveStats)> {
+    let start = std::time::Instant::now();
+    let mut stats = SolveStats::default();
+    let mut search = BumpSearch::empty();
+
+    for bump in ALL_BUMPS {
+        stats.bumps_tried += 1;
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let found = search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            stats.combinations_tried += 1;
+            solution.difficulty_with(mem.algo) >= difficulty
+        });
+        if let Some(solution) = found {
+            stats.elapsed = start.elapsed();
+            return Some((solution, stats));
+        }
+    }
+    None
+}
+
+/// Solve trying the best-covered bumps first (`SolverMemory::bumps_by_coverage`).
+///
+/// Better-covered bumps offer more candidates per group, so they are more likely to cover
+/// the data and to reach the difficulty within their search. Still deterministic.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_by_coverage(data: &[u8; 128], mem: &SolverMemory, difficulty: u32) -> Option<Solution> {
+    solve_with_memory_bumps(data, mem, difficulty, mem.bumps_by_coverage())
+}
+
+/// Solve using tables for a subset of bumps, searching them in ascending order.
+#[cfg(feature = "alloc")]
+pub fn solve_with_partial_memory(
+    data: &[u8; 128],
+    mem: &PartialSolverMemory,
+    difficulty: u32,
+) -> Option<Solution> {
+    let mut search = BumpSearch::empty();
+    for (bump, table) in mem.bumps().zip(mem.tables.iter()) {
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        if let Some(solution) = search.find(difficulty, mem.algo, &SolveLimits::UNLIMITED, None) {
+            return Some(solution);
+        }
+    }
+    None
+}
+
+/// Bytes of `data` that no seed of any bump can produce, or None if some bump covers every group.
+///
+/// Some(list) means no solution exists at any difficulty. Each `(group, value)` is listed once;
+/// the list is empty when every byte is reachable alone but no seed reaches all 8 of a group.
+#[cfg(feature = "alloc")]
+pub fn unreachable_targets(mem: &SolverMemory, data: &[u8; 128]) -> Option<Vec<(usize, u8)>> {
+    unreachable_in(mem.tables.iter().map(|table| &table.present[..]), data)
+}
+
+/// Whether any of the per-bump present bitsets has a covering seed for every group.
+#[cfg(feature = "alloc")]
+fn any_bump_covers<'a>(mut tables: impl Iterator<Item = &'a [[u8; 32]]>, data: &[u8; 128]) -> bool {
+    tables.any(|present| (0..16).all(|g| present_covers_group(present, data, g, 8)))
+}
+
+#[cfg(feature = "alloc")]
+fn gaps_in<'a>(tables: impl Iterator<Item = &'a [[u8; 32]]>) -> Vec<(u8, u8)> {
+    let mut out = Vec::new();
+    for (bump, present) in tables.enumerate() {
+        let mut reachable = [0u8; 32];
+        for row in present {
+            for (acc, bits) in reachable.iter_mut().zip(row) {
+                *acc |= bits;
+            }
+        }
+        let unreachable = reachable.map(|bits| !bits);
+        out.extend(present_targets(&unreachable).map(|target| (bump as u8, target)));
+    }
+    out
+}
+
+#[cfg(feature = "alloc")]
+fn unreachable_in<'a>(tables: impl Iterator<Item = &'a [[u8; 32]]> + Clone, data: &[u8; 128]) -> Option<Vec<(usize, u8)>> {
+    if any_bump_covers(tables.clone(), data) {
+        return None;
+    }
+
+    let mut reachable = [0u8; 32];
+    for present in tables.flat_map(|present| present.iter()) {
+        for (acc, bits) in reachable.iter_mut().zip(present) {
+            *acc |= bits;
+        }
+    }
+
+    let mut out = Vec::new();
+    for g in 0..16 {
+        for value in group_need(data, g, 8) {
+            if !bit_test(&reachable, value) && !out.contains(&(g, value)) {
+                out.push((g, value));
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Solve using a precomputed all-bumps table, reporting why no solution was found.
+///
+/// Returns `NoCoverage` when no bump has a covering seed for every group, naming the group
+/// that blocked the most bumps, and `DifficultyUnreached` when some bump covered the data
+/// but no combination met `difficulty`.
+#[cfg(feature = "alloc")]
+pub fn try_solve_with_memory(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+) -> Result<Solution, PackxError> {
+    try_solve_in(&mut BumpSearch::empty(), data, mem, ALL_BUMPS, difficulty, &SolveLimits::UNLIMITED, None)
+}
+
+/// `try_solve_with_memory` for `pubkey`'s data, failing with `PubkeyMismatch` if `mem` records
+/// a different pubkey. Memories that don't know their pubkey are trusted.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_checked(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+) -> Result<Solution, PackxError> {
+    if mem.pubkey().is_some_and(|built_for| built_for != pubkey) {
+        return Err(PackxError::PubkeyMismatch);
+    }
+    try_solve_with_memory(data, mem, difficulty)
+}
+
+/// Solve using a precomputed all-bumps table, returning None promptly once `cancel` is set.
+///
+/// The flag is checked before each bump and every `CANCEL_POLL_INTERVAL` combinations.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_cancellable(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    cancel: &AtomicBool,
+) -> Option<Solution> {
+    try_solve_in(&mut BumpSearch::empty(), data, mem, ALL_BUMPS, difficulty, &SolveLimits::UNLIMITED, Some(cancel)).ok()
+}
+
+/// Solve using a precomputed all-bumps table, giving up on each bump after `limits.max_iterations`
+/// combinations. Gives predictable latency where an unbounded search could run indefinitely.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_limited(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    limits: &SolveLimits,
+) -> Option<Solution> {
+    try_solve_in(&mut BumpSearch::empty(), data, mem, ALL_BUMPS, difficulty, limits, None).ok()
+}
+
+/// Combinations tried between clock reads in `solve_with_deadline`.
+#[cfg(feature = "std")]
+pub const DEADLINE_POLL_INTERVAL: usize = 1024;
+
+/// Solve using a precomputed all-bumps table, giving up once `deadline` passes.
+///
+/// The clock is read before each bump and every `DEADLINE_POLL_INTERVAL` combinations, so
+/// the solve overruns the deadline by at most that many hashes. When time runs out, returns
+/// None, or with `best_effort` the highest-difficulty solution seen so far (below
+/// `difficulty`, and None if no bump covered the data in time). Exhausting every bump before
+/// the deadline returns None either way, as `solve_with_memory` would.
+#[cfg(feature = "std")]
+pub fn solve_with_deadline(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    deadline: std::time::Instant,
+    best_effort: bool,
+) -> Option<Solution> {
+    let mut best: Option<(Solution, u32)> = None;
+    let mut search = BumpSearch::empty();
+    let mut expired = false;
+    for bump in ALL_BUMPS {
+        if std::time::Instant::now() >= deadline {
+            expired = true;
+            break;
+        }
+        if search.fill(data, bump, mem.tables[bump as usize].view()).is_err() {
+            continue;
+        }
+
+        let mut tried = 0usize;
+        let found = search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            let d = solution.difficulty_with(mem.algo);
+            if d >= difficulty {
+                return true;
+            }
+            if best_effort && best.as_ref().is_none_or(|&(_, best_d)| d > best_d) {
+                best = Some((*solution, d));
+            }
+            tried += 1;
+            expired = tried.is_multiple_of(DEADLINE_POLL_INTERVAL) && std::time::Instant::now() >= deadline;
+            expired
+        });
+        if expired {
+            break;
+        }
+        if found.is_some() {
+            return found;
+        }
+    }
+    if expired && best_effort {
+        best.map(|(solution, _)| solution)
+    } else {
+        None
+    }
+}
+
+/// Solve using a precomputed all-bumps table, accepting only solutions whose difficulty is
+/// within `min_difficulty..=max_difficulty`; over-powered solutions are skipped like weak ones.
+///
+/// Returns None when the range is empty or no combination lands in it.
+#[cfg(feature = "alloc")]
+pub fn solve_in_range(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    min_difficulty: u32,
+    max_difficulty: u32,
+) -> Option<Solution> {
+    if min_difficulty > max_difficulty {
+        return None;
+    }
+    let mut search = BumpSearch::empty();
+    ALL_BUMPS.into_iter().find_map(|bump| {
+        search.fill(data, bump, mem.tables[bump as usize].view()).ok()?;
+        search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            (min_difficulty..=max_difficulty).contains(&solution.difficulty_with(mem.algo))
+        })
+    })
+}
+
+/// Solve using a precomputed all-bumps table, reconstructing only the bytes of `data` whose
+/// bit is set in `mask` (bit `i & 7` of `mask[i >> 3]` for byte `i`, so group `g` is
+/// `mask[g]`). The other bytes are don't-care: they get nonce 0 and unpack to whatever that
+/// hashes to, so any seed may cover them.
+///
+/// An all-`0xFF` mask returns the same solution as `solve_with_memory`. Check results with
+/// `verify_masked`; plain `verify` fails wherever a don't-care byte differs from `data`.
+#[cfg(feature = "alloc")]
+pub fn solve_masked(data: &[u8; 128], mask: [u8; 16], mem: &SolverMemory, difficulty: u32) -> Option<Solution> {
+    let mut search = BumpSearch::empty();
+    ALL_BUMPS.into_iter().find_map(|bump| {
+        search.fill_masked(data, &mask, bump, mem.tables[bump as usize].view()).ok()?;
+        search.find(difficulty, mem.algo, &SolveLimits::UNLIMITED, None)
+    })
+}
+
+/// Where a paused solve stopped: the next combination to try is number `index`, in
+/// `solve_with_memory` order, of bump `bump`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveCursor {
+    pub bump: u8,
+    pub index: u128,
+}
+
+impl SolveCursor {
+    /// Serialized size: bump, then index as 16 little-endian bytes.
+    pub const SIZE: usize = 17;
+
+    /// The beginning of the search.
+    pub const START: SolveCursor = SolveCursor { bump: 0, index: 0 };
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0] = self.bump;
+        out[1..].copy_from_slice(&self.index.to_le_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let mut index = [0u8; 16];
+        index.copy_from_slice(&bytes[1..]);
+        SolveCursor { bump: bytes[0], index: u128::from_le_bytes(index) }
+    }
+}
+
+/// Result of a solve that may stop partway.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveOutcome {
+    Found(Solution),
+    /// The iteration budget ran out; pass the cursor to `resume_solve` to continue.
+    Paused(SolveCursor),
+    /// Every combination of every bump was tried without meeting the difficulty.
+    Exhausted,
+}
+
+/// Continue the `solve_with_memory` search from `cursor`, hashing at most `extra_iters`
+/// combinations in total before pausing.
+///
+/// Chained calls, each resuming from the previous `Paused` cursor, visit exactly the
+/// combinations one unbounded `solve_with_memory` would, in the same order, so they find
+/// the same solution. Start from `SolveCursor::START`.
+#[cfg(feature = "alloc")]
+pub fn resume_solve(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    cursor: SolveCursor,
+    extra_iters: u64,
+) -> SolveOutcome {
+    let mut search: BumpSearch = BumpSearch::empty();
+    let mut budget = extra_iters as u128;
+    let mut start = cursor.index;
+
+    for bump in cursor.bump..=u8::MAX {
+        if budget == 0 {
+            return SolveOutcome::Paused(SolveCursor { bump, index: start });
+        }
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_ok() {
+            let total = search.combinations().map_or(0, |iter| iter.total());
+            let len = total.saturating_sub(start).min(budget);
+            let accept = |solution: &Solution| solution.difficulty_with(mem.algo) >= difficulty;
+            if let Some(solution) = search.find_range_by(start, len, None, accept) {
+                return SolveOutcome::Found(solution);
+            }
+            budget -= len;
+            if start + len < total {
+                return SolveOutcome::Paused(SolveCursor { bump, index: start + len });
+            }
+        }
+        start = 0;
+    }
+    SolveOutcome::Exhausted
+}
+
+/// `try_solve_with_memory` using caller-owned candidate storage.
+#[cfg(feature = "alloc")]
+fn try_solve_in<const GROUPS: usize, const BYTES: usize>(
+    search: &mut BumpSearch<GROUPS, BYTES>,
+    data: &[u8; BYTES],
+    mem: &SolverMemory,
+    bumps: impl IntoIterator<Item = u8>,
+    difficulty: u32,
+    limits: &SolveLimits,
+    cancel: Option<&AtomicBool>,
+) -> Result<Solution<GROUPS, BYTES>, PackxError> {
+    let mut uncovered = [0usize; GROUPS];
+    let mut covered_any = false;
+
+    for bump in bumps {
+        if is_cancelled(cancel) {
+            return Err(PackxError::Cancelled);
+        }
+        let table: &SeedTable = &mem.tables[bump as usize];
+        match search.fill(data, bump, table.view()) {
+            Ok(()) => {
+                covered_any = true;
+                if let Some(solution) = search.find(difficulty, mem.algo, limits, cancel) {
+                    return Ok(solution);
+                }
+            }
+            Err(group) => uncovered[group] += 1,
+        }
+    }
+
+    if covered_any {
+        return Err(PackxError::DifficultyUnreached);
+    }
+    let group = (0..GROUPS)
+        .max_by_key(|&g| (uncovered[g], core::cmp::Reverse(g)))
+        .unwrap_or(0);
+    Err(PackxError::NoCoverage { group })
+}
+
+/// Bumps whose tables have a covering seed for every group of `data`, ascending. Only
+/// reads the present bitsets; an empty result means `data` can't be packed at all.
+///
+/// Not worth calling before a solve: the search already runs this check per bump before
+/// gathering any nonces, and usually succeeds on the first bump, while this scans all 256.
+#[cfg(feature = "alloc")]
+pub fn covering_bumps(data: &[u8; 128], mem: &SolverMemory) -> Vec<u8> {
+    ALL_BUMPS
+        .filter(|&bump| (0..16).all(|g| group_has_coverage(data, g, 8, mem.tables[bump as usize].view())))
+        .collect()
+}
+
+/// Reusable solver state for packing many chunks against one `SolverMemory`.
+///
+/// Holds the 16 per-group candidate lists and refills them on every call instead of
+/// allocating, much like reusing a hasher. `solve` takes `&mut self`, so one `Solver` can't
+/// be shared across threads; create one per thread instead, e.g. with rayon's
+/// `for_each_init(Solver::new, ...)`.
+#[cfg(feature = "alloc")]
+pub struct Solver {
+    search: BumpSearch,
+}
+
+#[cfg(feature = "alloc")]
+impl Solver {
+    pub fn new() -> Self {
+        Solver {
+            search: BumpSearch::empty(),
+        }
+    }
+
+    /// Same result as `solve_with_memory`, without per-call allocation once warmed up.
+    pub fn solve(&mut self, data: &[u8; 128], mem: &SolverMemory, difficulty: u32) -> Option<Solution> {
+        self.try_solve(data, mem, difficulty).ok()
+    }
+
+    /// Same result as `try_solve_with_memory`, without per-call allocation once warmed up.
+    pub fn try_solve(
+        &mut self,
+        data: &[u8; 128],
+        mem: &SolverMemory,
+        difficulty: u32,
+    ) -> Result<Solution, PackxError> {
+        try_solve_in(&mut self.search, data, mem, ALL_BUMPS, difficulty, &SolveLimits::UNLIMITED, None)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run `op` on the current rayon pool for a `num_threads` of 0, otherwise on a pool of
+/// `num_threads` threads built for this call. None if that pool can't be built.
+#[cfg(feature = "rayon")]
+fn in_pool<R: Send>(num_threads: usize, op: impl FnOnce() -> R + Send) -> Option<R> {
+    if num_threads == 0 {
+        return Some(op());
+    }
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().ok()?;
+    Some(pool.install(op))
+}
+
+/// Solve using a precomputed all-bumps table, searching bumps concurrently on a rayon pool.
+///
+/// Any valid solution may be returned, so the result can differ run-to-run depending on
+/// thread scheduling. Use `solve_with_memory` when a deterministic result is required.
+///
+/// A `num_threads` of 0 runs on the current pool: rayon's global one, or the caller's inside
+/// `ThreadPool::install`. Any other count spawns a pool of that size for this call alone,
+/// which costs more than an easy solve, so callers solving many chunks should pass 0. Returns
+/// None if that pool can't be built.
+#[cfg(feature = "rayon")]
+pub fn solve_with_memory_parallel(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    num_threads: usize,
+) -> Option<Solution> {
+    use rayon::prelude::*;
+
+    let found = AtomicBool::new(false);
+    in_pool(num_threads, || {
+        (0u8..=u8::MAX).into_par_iter().find_map_any(|bump| {
+            let table: &SeedTable = &mem.tables[bump as usize];
+            let solution = search_one_bump(data, bump, table, difficulty, mem.algo, Some(&found))?;
+            found.store(true, Ordering::Relaxed);
+            Some(solution)
+        })
+    })
+    .flatten()
+}
+
+/// Totals above this aren't split by `solve_one_bump_parallel`. Past it the count may have
+/// saturated, and each slice would hold far more combinations than could ever be scanned.
+#[cfg(feature = "rayon")]
+pub const PARALLEL_SPLIT_MAX: u128 = u64::MAX as u128;
+
+/// Solve one bump by splitting its combination index range across a rayon pool.
+///
+/// Helps when only one bump covers the data and its search is long. Like
+/// `solve_with_memory_parallel`, any valid solution may be returned. Falls back to the
+/// sequential `solve_one_bump` scan when the total exceeds `PARALLEL_SPLIT_MAX`.
+/// `num_threads` picks the pool as in `solve_with_memory_parallel`, and the range is split
+/// into one slice per thread of that pool.
+#[cfg(feature = "rayon")]
+pub fn solve_one_bump_parallel(
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    num_threads: usize,
+) -> Option<Solution> {
+    use rayon::prelude::*;
+
+    let search = BumpSearch::new(data, bump, table.view()).ok()?;
+    let total = search.combinations()?.total();
+    if total > PARALLEL_SPLIT_MAX {
+        return search.find(difficulty, HashAlgo::Blake3, &SolveLimits::UNLIMITED, None);
+    }
+
+    let found = AtomicBool::new(false);
+    in_pool(num_threads, || {
+        let slices = rayon::current_num_threads() as u128;
+        (0..slices).into_par_iter().find_map_any(|k| {
+            let start = k * total / slices;
+            let end = (k + 1) * total / slices;
+            let solution = search.find_range_by(start, end - start, Some(&found), |solution| {
+                solution.difficulty() >= difficulty
+            })?;
+            found.store(true, Ordering::Relaxed);
+            Some(solution)
+        })
+    })
+    .flatten()
+}
+
+/// A solution together with the difficulty hash computed while finding it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackResult {
+    pub solution: Solution,
+    /// Same as `solution.bump`.
+    pub bump: u8,
+    /// Leading zero bits of `hash`, at least the requested minimum.
+    pub difficulty: u32,
+    /// H(serialize(solution)) under the memory's hash algorithm.
+    pub hash: [u8; 32],
+}
+
+/// Build tables for `pubkey` and solve, returning the difficulty and hash alongside the solution.
+#[cfg(feature = "alloc")]
+pub fn pack(pubkey: &[u8; 32], data: &[u8; 128], min_difficulty: u32) -> Option<PackResult> {
+    pack_with_memory(data, &build_memory(pubkey), min_difficulty)
+}
+
+/// `solve_with_memory`, keeping the hash the search already computed for the winning
+/// combination so callers don't serialize and hash it again.
+#[cfg(feature = "alloc")]
+pub fn pack_with_memory(data: &[u8; 128], mem: &SolverMemory, min_difficulty: u32) -> Option<PackResult> {
+    let mut search = BumpSearch::empty();
+    let mut hash = [0u8; 32];
+    for bump in ALL_BUMPS {
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let found = search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            hash = solution.solution_hash_with(mem.algo);
+            get_difficulty(hash) >= min_difficulty
+        });
+        if let Some(solution) = found {
+            return Some(PackResult { solution, bump, difficulty: get_difficulty(hash), hash });
+        }
+    }
+    None
+}
+
+/// `solve_one_bump` with difficulty measured under `mode`. `pubkey` is only hashed for
+/// `DataBound`, and must be the one `table` was built for to get a solution `verify_with_mode`
+/// accepts.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_with_mode(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    bump: u8,
+    table: &SeedTable,
+    difficulty: u32,
+    mode: DifficultyMode,
+) -> Option<Solution> {
+    let search = BumpSearch::new(data, bump, table.view()).ok()?;
+    search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+        get_difficulty(mode.hash(pubkey, data, solution)) >= difficulty
+    })
+}
+
+/// `solve_with_memory` with difficulty measured under `mode`, trying bumps in order.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_mode(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    difficulty: u32,
+    mode: DifficultyMode,
+) -> Option<Solution> {
+    let mut search = BumpSearch::empty();
+    for bump in ALL_BUMPS {
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let found = search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            get_difficulty(mode.hash_with(mem.algo, pubkey, data, solution)) >= difficulty
+        });
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// Solve using a precomputed all-bumps table, accepting the first solution whose hash
+/// is at most `target` (see `meets_target`) instead of counting leading zero bits.
+#[cfg(feature = "alloc")]
+pub fn solve_to_target(data: &[u8; 128], mem: &SolverMemory, target: &[u8; 32]) -> Option<Solution> {
+    let mut search = BumpSearch::empty();
+    for bump in 0u8..=u8::MAX {
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let found = search.find_by(&SolveLimits::UNLIMITED, None, |solution| {
+            meets_target(&solution.solution_hash_with(mem.algo), target)
+        });
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// Search every bump for the highest-difficulty solution, trying at most
+/// `max_iterations` combinations per bump. Returns the best solution with its difficulty,
+/// or None if no bump covers the data.
+#[cfg(feature = "alloc")]
+pub fn solve_best_with_memory(
+    data: &[u8; 128],
+    mem: &SolverMemory,
+    max_iterations: usize,
+) -> Option<(Solution, u32)> {
+    let mut best: Option<(Solution, u32)> = None;
+    let mut search = BumpSearch::empty();
+    for bump in 0u8..=u8::MAX {
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let Some(iter) = search.combinations() else { continue };
+
+        for idxs_ordered in iter.take(max_iterations) {
+            let solution = search.solution(&idxs_ordered);
+            let difficulty = solution.difficulty_with(mem.algo);
+            if best.as_ref().is_none_or(|&(_, d)| difficulty > d) {
+                best = Some((solution, difficulty));
+            }
+        }
+    }
+    best
+}
+
+/// Lazy iterator over every solution meeting a difficulty, across all bumps in order.
+///
+/// Combinations are generated on demand, so the cartesian product is never materialized.
+#[cfg(feature = "alloc")]
+pub struct SolutionIter<'a> {
+    data: [u8; 128],
+    mem: &'a SolverMemory,
+    difficulty: u32,
+    next_bump: usize,
+    current: Option<(BumpSearch, MixedRadixCounter)>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for SolutionIter<'_> {
+    type Item = Solution;
+
+    fn next(&mut self) -> Option<Solution> {
+        loop {
+            if let Some((search, iter)) = &mut self.current {
+                for idxs_ordered in iter.by_ref() {
+                    let solution = search.solution(&idxs_ordered);
+                    if solution.difficulty_with(self.mem.algo) >= self.difficulty {
+                        return Some(solution);
+                    }
+                }
+                self.current = None;
+            }
+
+            if self.next_bump > u8::MAX as usize {
+                return None;
+            }
+            let bump = self.next_bump as u8;
+            self.next_bump += 1;
+
+            let table: &SeedTable = &self.mem.tables[bump as usize];
+            if let Ok(search) = BumpSearch::new(&self.data, bump, table.view()) {
+                if let Some(iter) = search.combinations() {
+                    self.current = Some((search, iter));
+                }
+            }
+        }
+    }
+}
+
+/// Enumerate all solutions for `data` meeting `difficulty`, bump by bump.
+#[cfg(feature = "alloc")]
+pub fn solutions_with_memory<'a>(
+    data: &[u8; 128],
+    mem: &'a SolverMemory,
+    difficulty: u32,
+) -> SolutionIter<'a> {
+    SolutionIter {
+        data: *data,
+        mem,
+        difficulty,
+        next_bump: 0,
+        current: None,
+    }
+}
+
+/// Solve a 64-byte half chunk using a precomputed all-bumps table. The tables from
+/// `build_memory` serve both chunk sizes.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory64(data: &[u8; 64], mem: &SolverMemory, difficulty: u32) -> Option<Solution64> {
+    solve_with_memory_groups(data, mem, difficulty).ok()
+}
+
+/// Build the precompute for this pubkey, then solve a 64-byte half chunk.
+#[cfg(feature = "alloc")]
+pub fn solve64(pubkey: &[u8; 32], data: &[u8; 64], difficulty: u32) -> Option<Solution64> {
+    solve_with_memory64(data, &build_memory(pubkey), difficulty)
+}
+
+/// `verify` for a 64-byte half chunk.
+pub fn verify64(pubkey: &[u8; 32], data: &[u8; 64], solution: &Solution64, difficulty: u32) -> bool {
+    verify(pubkey, data, solution, difficulty)
+}
+
+/// Solve a 128-byte chunk in 4-byte groups using a precomputed all-bumps table.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_g4(data: &[u8; 128], mem: &SolverMemory, difficulty: u32) -> Option<SolutionG4> {
+    solve_with_memory_groups(data, mem, difficulty).ok()
+}
+
+/// Build the precompute for this pubkey, then solve a chunk in 4-byte groups.
+#[cfg(feature = "alloc")]
+pub fn solve_g4(pubkey: &[u8; 32], data: &[u8; 128], difficulty: u32) -> Option<SolutionG4> {
+    solve_with_memory_g4(data, &build_memory(pubkey), difficulty)
+}
+
+/// `verify` for a chunk in 4-byte groups.
+pub fn verify_g4(pubkey: &[u8; 32], data: &[u8; 128], solution: &SolutionG4, difficulty: u32) -> bool {
+    verify(pubkey, data, solution, difficulty)
+}
+
+/// Solve by first building the precompute for this pubkey, then searching.
+#[cfg(feature = "alloc")]
+pub fn solve(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    difficulty: u32,
+) -> Option<Solution> {
+    let mem = build_memory(pubkey);
+    solve_with_memory(data, &mem, difficulty)
+}
+
+/// Packs arbitrary-length data into 128-byte chunks, reusing one precompute for all of them.
+///
+/// Input is split into 128-byte chunks and the final short chunk is padded with zeros.
+/// The caller keeps the original length and passes it to `unpack_slice` to drop the padding.
+#[cfg(feature = "alloc")]
+pub struct Packer {
+    pubkey: [u8; 32],
+    mem: SolverMemory,
+    difficulty: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl Packer {
+    /// Build the precompute for `pubkey` once; every chunk is solved at `difficulty`.
+    pub fn new(pubkey: &[u8; 32], difficulty: u32) -> Self {
+        Packer {
+            pubkey: *pubkey,
+            mem: build_memory(pubkey),
+            difficulty,
+        }
+    }
+
+    pub fn pubkey(&self) -> &[u8; 32] {
+        &self.pubkey
+    }
+
+    pub fn memory(&self) -> &SolverMemory {
+        &self.mem
+    }
+
+    /// Pack `data` into one solution per 128-byte chunk, zero-padding the tail.
+    /// Empty input yields no solutions. Returns None if any chunk can't be solved.
+    pub fn pack_slice(&self, data: &[u8]) -> Option<Vec<Solution>> {
+        data.chunks(128)
+            .map(|chunk| solve_with_memory(&pad_chunk(chunk), &self.mem, self.difficulty))
+            .collect()
+    }
+}
+
+/// Copy a chunk of at most 128 bytes into a zero-padded block.
+#[cfg(feature = "alloc")]
+fn pad_chunk(chunk: &[u8]) -> [u8; 128] {
+    let mut block = [0u8; 128];
+    block[..chunk.len()].copy_from_slice(chunk);
+    block
+}
+
+/// Pack arbitrary-length data in parallel: build the precompute once, split into
+/// zero-padded 128-byte chunks, and solve them on rayon with one `Solver` per thread.
+/// Without the `rayon` feature the chunks are solved one after another instead.
+///
+/// Solutions are returned in chunk order; unpack with `unpack_slice(pubkey, &solutions, data.len())`.
+/// Fails with `ChunkUnsolved` naming the first chunk that couldn't reach `difficulty`.
+#[cfg(feature = "alloc")]
+pub fn pack_file_parallel(pubkey: &[u8; 32], data: &[u8], difficulty: u32) -> Result<Vec<Solution>, PackxError> {
+    let mem = build_memory(pubkey);
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let results: Vec<Option<Solution>> = data
+            .par_chunks(128)
+            .map_init(Solver::new, |solver, chunk| solver.solve(&pad_chunk(chunk), &mem, difficulty))
+            .collect();
+
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(index, solution)| solution.ok_or(PackxError::ChunkUnsolved { index }))
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        pack_file_escalating_with_memory(data, &mem, |_| difficulty)
+    }
+}
+
+/// Pack arbitrary-length data with a per-chunk difficulty: chunk `i` (zero-padded like
+/// `Packer::pack_slice`) must reach `difficulty_fn(i)`, e.g. for proof of work that ramps
+/// up along a file.
+///
+/// Solutions are returned in chunk order. Fails with `ChunkUnsolved` naming the first chunk
+/// that couldn't reach its difficulty. Each chunk's search is unbounded, so a difficulty
+/// far above what the chunk's combinations can reach takes as long as exhausting them.
+#[cfg(feature = "alloc")]
+pub fn pack_file_escalating(
+    pubkey: &[u8; 32],
+    data: &[u8],
+    difficulty_fn: impl Fn(usize) -> u32,
+) -> Result<Vec<Solution>, PackxError> {
+    pack_file_escalating_with_memory(data, &build_memory(pubkey), difficulty_fn)
+}
+
+/// `pack_file_escalating` with tables built beforehand.
+#[cfg(feature = "alloc")]
+pub fn pack_file_escalating_with_memory(
+    data: &[u8],
+    mem: &SolverMemory,
+    difficulty_fn: impl Fn(usize) -> u32,
+) -> Result<Vec<Solution>, PackxError> {
+    let mut solver = Solver::new();
+    data.chunks(128)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let solution = solver.solve(&pad_chunk(chunk), mem, difficulty_fn(index));
+            solution.ok_or(PackxError::ChunkUnsolved { index })
+        })
+        .collect()
+}
+
+/// Difficulty of the first solution found at difficulty 0 for up to `samples` chunks, spread
+/// evenly over `data`. Useful for picking a difficulty most chunks meet without extra search.
+///
+/// Chunks are zero-padded like `Packer::pack_slice`. Chunks with no solution at all are skipped,
+/// so the result can be shorter than `samples`. Runs in parallel with the `rayon` feature.
+#[cfg(feature = "alloc")]
+pub fn difficulty_profile(pubkey: &[u8; 32], data: &[u8], samples: usize) -> Vec<u32> {
+    let num_chunks = data.len().div_ceil(128);
+    let picks = samples.min(num_chunks);
+    if picks == 0 {
+        return Vec::new();
+    }
+    let mem = build_memory(pubkey);
+    let chunk_at = |i: usize| {
+        let start = i * num_chunks / picks * 128;
+        pad_chunk(&data[start..data.len().min(start + 128)])
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        (0..picks)
+            .into_par_iter()
+            .map_init(Solver::new, |solver, i| solver.solve(&chunk_at(i), &mem, 0))
+            .flatten()
+            .map(|solution| solution.difficulty())
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut solver = Solver::new();
+        (0..picks)
+            .filter_map(|i| solver.solve(&chunk_at(i), &mem, 0))
+            .map(|solution| solution.difficulty())
+            .collect()
+    }
+}
+
+/// Highest difficulty `solve_with_memory_limited` reaches for `data` when each bump is capped
+/// at `max_iters` combinations. Builds the tables for `pubkey` once.
+#[cfg(feature = "alloc")]
+pub fn max_solvable_difficulty(pubkey: &[u8; 32], data: &[u8; 128], max_iters: usize) -> u32 {
+    max_solvable_difficulty_with_memory(data, &build_memory(pubkey), max_iters)
+}
+
+/// `max_solvable_difficulty` using a precomputed all-bumps table.
+///
+/// A capped solve at difficulty `d` succeeds exactly when some bump's first `max_iters`
+/// combinations include one of difficulty `d` or more, so one pass recording the best
+/// difficulty per bump gives the same answer as solving at each level in turn.
+/// Returns 0 when no bump can reconstruct `data`.
+#[cfg(feature = "alloc")]
+pub fn max_solvable_difficulty_with_memory(data: &[u8; 128], mem: &SolverMemory, max_iters: usize) -> u32 {
+    let mut search: BumpSearch = BumpSearch::empty();
+    let limits = SolveLimits::max_iterations(max_iters);
+    let mut best = 0u32;
+    for bump in ALL_BUMPS {
+        let table: &SeedTable = &mem.tables[bump as usize];
+        if search.fill(data, bump, table.view()).is_err() {
+            continue;
+        }
+        let found = search.find_by(&limits, None, |solution| {
+            best = best.max(solution.difficulty_with(mem.algo));
+            best == MAX_DIFFICULTY
+        });
+        if found.is_some() {
+            break;
+        }
+    }
+    best
+}
+
+/// Reconstruct packed data and truncate it to `original_len`, dropping the zero padding.
+/// If `original_len` exceeds the packed capacity, the full `128 * solutions.len()` bytes are returned.
+#[cfg(feature = "alloc")]
+pub fn unpack_slice(pubkey: &[u8; 32], solutions: &[Solution], original_len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(solutions.len() * 128);
+    for solution in solutions {
+        out.extend_from_slice(&unpack(pubkey, solution));
+    }
+    out.truncate(original_len);
+    out
+}
+
+/// Reconstruct data using H(pubkey, bump, seed, nonce).
+pub fn unpack<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    solution: &Solution<GROUPS, BYTES>,
+) -> [u8; BYTES] {
+    unpack_with_params(pubkey, solution, &HashParams::DEFAULT)
+}
+
+/// Reconstruct data using custom hash parameters.
+pub fn unpack_with_params<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    solution: &Solution<GROUPS, BYTES>,
+    params: &HashParams,
+) -> [u8; BYTES] {
+    let width = Solution::<GROUPS, BYTES>::GROUP_BYTES;
+    let mut data = [0u8; BYTES];
+    for g in 0..GROUPS {
+        let seed = solution.seeds[g];
+        let cs = g * width;
+        for i in 0..width {
+            let nonce = solution.nonces[cs + i];
+            data[cs + i] = params.h0(pubkey, solution.bump, seed, nonce);
+        }
+    }
+    data
+}
+
+/// Unpack and compare against `expected`, reporting the first byte that diverged.
+///
+/// Unlike `verify` this ignores difficulty; it only checks the reconstruction.
+pub fn unpack_checked<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    expected: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+) -> Result<[u8; BYTES], UnpackError> {
+    let data = unpack(pubkey, solution);
+    match data.iter().zip(expected).position(|(a, e)| a != e) {
+        None => Ok(data),
+        Some(index) => Err(UnpackError {
+            group: index / Solution::<GROUPS, BYTES>::GROUP_BYTES,
+            index,
+            expected: expected[index],
+            actual: data[index],
+        }),
+    }
+}
+
+/// Whether `solution` unpacks to `data`, comparing byte by byte and stopping at the first
+/// mismatch, so garbage is rejected after one hash rather than 128.
+fn reconstructs<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    data: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+    params: &HashParams,
+) -> bool {
+    let width = Solution::<GROUPS, BYTES>::GROUP_BYTES;
+    data.iter().zip(&solution.nonces).enumerate().all(|(i, (&expected, &nonce))| {
+        params.h0(pubkey, solution.bump, solution.seeds[i / width], nonce) == expected
+    })
+}
+
+/// Check reconstruction and difficulty.
+pub fn verify<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    data: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+    difficulty: u32,
+) -> bool {
+    verify_with_params(pubkey, data, solution, difficulty, &HashParams::DEFAULT)
+}
+
+/// Check only that `solution` unpacks to `data`, skipping the serialize-and-hash of the
+/// difficulty check for callers who enforce difficulty elsewhere. Stops at the first
+/// mismatched byte, like `verify`.
+pub fn verify_reconstruction<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    data: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+) -> bool {
+    reconstructs(pubkey, data, solution, &HashParams::DEFAULT)
+}
+
+/// `verify` for a solution distributed with its claimed hash: also rejects it unless
+/// BLAKE3(serialize(solution)) equals `expected_hash`. Difficulty is counted on the recomputed
+/// hash, so a hash that lies about the solution never vouches for it.
+pub fn verify_with_hash<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    data: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+    expected_hash: &[u8; 32],
+    difficulty: u32,
+) -> bool {
+    let hash = solution.solution_hash();
+    hash == *expected_hash && get_difficulty(hash) >= difficulty && reconstructs(pubkey, data, solution, &HashParams::DEFAULT)
+}
+
+/// Check reconstruction and difficulty of a solution in its 145-byte wire form.
+/// Allocation-free, and hashes the given bytes directly instead of reserializing.
+pub fn verify_bytes(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    solution_bytes: &[u8; SOLUTION_SIZE],
+    difficulty: u32,
+) -> bool {
+    let solution = deserialize(solution_bytes);
+    if !reconstructs(pubkey, data, &solution, &HashParams::DEFAULT) {
+        return false;
+    }
+    difficulty_from_bytes(solution_bytes) >= difficulty
+}
+
+/// Check reconstruction and difficulty using custom hash parameters.
+pub fn verify_with_params<const GROUPS: usize, const BYTES: usize>(
+    pubkey: &[u8; 32],
+    data: &[u8; BYTES],
+    solution: &Solution<GROUPS, BYTES>,
+    difficulty: u32,
+    params: &HashParams,
+) -> bool {
+    if !reconstructs(pubkey, data, solution, params) {
+        return false;
+    }
+    solution.difficulty_with(params.algo()) >= difficulty
+}
+
+/// Check difficulty and the reconstruction of only the bytes set in `mask`, laid out as in
+/// `solve_masked`.
+pub fn verify_masked(pubkey: &[u8; 32], data: &[u8; 128], mask: &[u8; 16], solution: &Solution, difficulty: u32) -> bool {
+    let params = HashParams::DEFAULT;
+    let reconstructed = (0..128).filter(|&i| mask[i >> 3] >> (i & 7) & 1 != 0).all(|i| {
+        params.h0(pubkey, solution.bump, solution.seeds[i / 8], solution.nonces[i]) == data[i]
+    });
+    reconstructed && solution.difficulty() >= difficulty
+}
+
+/// Check reconstruction, and difficulty measured under `mode`.
+pub fn verify_with_mode(
+    pubkey: &[u8; 32],
+    data: &[u8; 128],
+    solution: &Solution,
+    difficulty: u32,
+    mode: DifficultyMode,
+) -> bool {
+    reconstructs(pubkey, data, solution, &HashParams::DEFAULT) && mode.difficulty(pubkey, data, solution) >= difficulty
+}
+
+/// Whether `bump` is the canonical bump of the program-derived address for `seeds` under
+/// `program_id`, i.e. the one `Pubkey::find_program_address` returns.
+///
+/// `seeds` are the PDA seeds without the bump byte, exactly as passed to
+/// `find_program_address`: at most 16 slices of at most 32 bytes each. Pair it with `verify`
+/// to reject solutions whose bump is valid for packx but non-canonical for the PDA. The
+/// search re-derives the address from bump 255 down, which costs compute units on-chain.
+#[cfg(feature = "solana")]
+pub fn verify_bump_canonical(program_id: &solana_program::pubkey::Pubkey, seeds: &[&[u8]], bump: u8) -> bool {
+    solana_program::pubkey::Pubkey::try_find_program_address(seeds, program_id)
+        .is_some_and(|(_, canonical)| canonical == bump)
+}
+
+/// `verify` over many chunks for one pubkey, returning one result per item in order.
+///
+/// With the `rayon` feature the items are checked in parallel.
+#[cfg(feature = "alloc")]
+pub fn verify_batch(pubkey: &[u8; 32], items: &[(&[u8; 128], &Solution)], difficulty: u32) -> Vec<bool> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items
+            .par_iter()
+            .map(|(data, solution)| verify(pubkey, data, solution, difficulty))
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        items
+            .iter()
+            .map(|(data, solution)| verify(pubkey, data, solution, difficulty))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use rand::RngCore;
 
-    const TEST_DIFFICULTY: u32 = 1;
-    const TEST_BUMP_TRIES: u8 = 7;
+    const TEST_DIFFICULTY: u32 = 1;
+    const TEST_BUMP_TRIES: u8 = 7;
+
+    /// Full all-bumps memory for a fixed pubkey, built once and shared across tests.
+    fn shared_memory() -> &'static ([u8; 32], SolverMemory) {
+        static MEM: std::sync::OnceLock<([u8; 32], SolverMemory)> = std::sync::OnceLock::new();
+        MEM.get_or_init(|| {
+            let pubkey = [7u8; 32];
+            (pubkey, build_memory(&pubkey))
+        })
+    }
+
+    fn solve_lightweight(pubkey: &[u8; 32], data: &[u8; 128], difficulty: u32) -> Option<Solution> {
+        for bump in 0u8..=TEST_BUMP_TRIES {
+            let table = build_one_bump(pubkey, bump);
+            if let Some(solution) = solve_one_bump(data, bump, &table, difficulty) {
+                return Some(solution);
+            }
+        }
+        None
+    }
+
+    /// Reference build that scans every nonce without the saturation break.
+    fn build_one_bump_exhaustive(pubkey: &[u8; 32], bump: u8) -> Box<SeedTable> {
+        let mut table = Box::new(SeedTable {
+            nonces: vec![[0u8; 256]; 256].into_boxed_slice(),
+            present: vec![[0u8; 32]; 256].into_boxed_slice(),
+        });
+        for seed in 0u8..=u8::MAX {
+            for nonce in 0u8..=u8::MAX {
+                let t = HashParams::DEFAULT.h0(pubkey, bump, seed, nonce);
+                if !bit_test(&table.present[seed as usize], t) {
+                    bit_set(&mut table.present[seed as usize], t);
+                    table.nonces[seed as usize][t as usize] = nonce;
+                }
+            }
+        }
+        table
+    }
+
+    #[test]
+    fn test_build_one_bump_early_exit_matches_exhaustive() {
+        let pubkey = [42u8; 32];
+        let bump = 3;
+        let fast = build_one_bump(&pubkey, bump);
+        let slow = build_one_bump_exhaustive(&pubkey, bump);
+        assert_eq!(fast.present, slow.present);
+        assert_eq!(fast.nonces, slow.nonces);
+    }
+
+    #[test]
+    fn test_solve_and_verify() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
+        assert!(verify(&pubkey, &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_serialize_deserialize_roundtrip() {
+        let solution = Solution { bump: 3, seeds: [9; 16], nonces: [7; 128] };
+        let ser = serialize(&solution);
+        let de = deserialize(&ser);
+        assert_eq!(solution.bump, de.bump);
+        assert_eq!(solution.seeds, de.seeds);
+        assert_eq!(solution.nonces, de.nonces);
+    }
+
+    #[test]
+    fn test_verify_failure_wrong_data() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
+        let mut wrong = data;
+        wrong[0] = wrong[0].wrapping_add(1);
+        assert!(!verify(&pubkey, &wrong, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_verify_failure_wrong_pubkey() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
+        let mut other = pubkey;
+        other[0] ^= 1;
+        assert!(!verify(&other, &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_hash_params_offset_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let params = HashParams::DEFAULT.with_offset(17);
+        let solution = (0u8..=TEST_BUMP_TRIES)
+            .find_map(|bump| {
+                let table = build_one_bump_with_params(&pubkey, bump, &params);
+                solve_one_bump(&data, bump, &table, TEST_DIFFICULTY)
+            })
+            .expect("solve failed");
+
+        assert_eq!(unpack_with_params(&pubkey, &solution, &params), data);
+        assert!(verify_with_params(&pubkey, &data, &solution, TEST_DIFFICULTY, &params));
+        assert!(!verify(&pubkey, &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_hash_params_domain_separates() {
+        let pubkey = [0x3Cu8; 32];
+        let data = [0x42u8; 128];
+        let params = HashParams::DEFAULT.with_domain(b"packx-test/v1");
+
+        let plain = build_one_bump(&pubkey, 0);
+        let empty = build_one_bump_with_params(&pubkey, 0, &HashParams::DEFAULT.with_domain(b""));
+        assert_eq!(empty.nonces, plain.nonces);
+        let separated = build_one_bump_with_params(&pubkey, 0, &params);
+        assert_ne!(separated.nonces, plain.nonces);
+
+        let solution = (0u8..=TEST_BUMP_TRIES)
+            .find_map(|bump| {
+                let table = build_one_bump_with_params(&pubkey, bump, &params);
+                solve_one_bump(&data, bump, &table, TEST_DIFFICULTY)
+            })
+            .expect("solve failed");
+
+        assert!(verify_with_params(&pubkey, &data, &solution, TEST_DIFFICULTY, &params));
+        assert!(!verify(&pubkey, &data, &solution, TEST_DIFFICULTY));
+        let other = HashParams::DEFAULT.with_domain(b"packx-test/v2");
+        assert!(!verify_with_params(&pubkey, &data, &solution, TEST_DIFFICULTY, &other));
+    }
+
+    #[cfg(feature = "sha256")]
+    #[test]
+    fn test_sha256_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let params = HashParams::DEFAULT.with_algo(HashAlgo::Sha256);
+        let solution = (0u8..=TEST_BUMP_TRIES)
+            .find_map(|bump| {
+                let table = build_one_bump_with_params(&pubkey, bump, &params);
+                solve_one_bump_with_algo(&data, bump, &table, TEST_DIFFICULTY, HashAlgo::Sha256)
+            })
+            .expect("solve failed");
+
+        assert_eq!(unpack_with_params(&pubkey, &solution, &params), data);
+        assert!(solution.difficulty_with(HashAlgo::Sha256) >= TEST_DIFFICULTY);
+        assert!(verify_with_params(&pubkey, &data, &solution, TEST_DIFFICULTY, &params));
+        assert!(!verify(&pubkey, &data, &solution, 0));
+    }
+
+    #[test]
+    fn test_bitset_byte_layout() {
+        let mut bits = [0u8; 32];
+        for t in [0u8, 7, 8, 63, 64, 130, 255] {
+            bit_set(&mut bits, t);
+        }
+        let mut expected = [0u8; 32];
+        expected[0] = 0x81;
+        expected[1] = 0x01;
+        expected[7] = 0x80;
+        expected[8] = 0x01;
+        expected[16] = 0x04;
+        expected[31] = 0x80;
+        assert_eq!(bits, expected);
+        assert_eq!(bit_count(&bits), 7);
+        assert!((0..=255).all(|t| bit_test(&bits, t) == (bits[(t >> 3) as usize] >> (t & 7) & 1 != 0)));
+
+        bit_clear(&mut bits, 63);
+        assert_eq!(bits[7], 0);
+        assert_eq!(bit_count(&[0xFF; 32]), 256);
+    }
+
+    #[test]
+    fn test_covers_all_matches_bit_tests() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        rng.fill_bytes(&mut pubkey);
+        let table = build_one_bump(&pubkey, 0);
+
+        for _ in 0..64 {
+            let mut need = [0u8; 8];
+            rng.fill_bytes(&mut need);
+            for present in table.present.iter() {
+                let expected = need.iter().all(|&t| bit_test(present, t));
+                assert_eq!(covers_all(present, &need), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_has_coverage_matches_candidates() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        rng.fill_bytes(&mut pubkey);
+        let table = build_one_bump(&pubkey, 0);
+
+        let mut cands = Vec::new();
+        for _ in 0..8 {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            for g in 0..16 {
+                fill_group_candidates(&data, g, 8, table.view(), &mut cands);
+                assert_eq!(group_has_coverage(&data, g, 8, table.view()), !cands.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_one_bump_limited_gives_up() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let limits = SolveLimits::max_iterations(1000);
+        for bump in 0u8..=TEST_BUMP_TRIES {
+            let table = build_one_bump(&pubkey, bump);
+            assert_eq!(solve_one_bump_limited(&data, bump, &table, 200, &limits), None);
+            assert_eq!(
+                solve_one_bump_limited(&data, bump, &table, 0, &limits),
+                solve_one_bump(&data, bump, &table, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_with_memory_cancellable() {
+        let (_, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let cancel = AtomicBool::new(false);
+        assert_eq!(
+            solve_with_memory_cancellable(&data, mem, TEST_DIFFICULTY, &cancel),
+            solve_with_memory(&data, mem, TEST_DIFFICULTY)
+        );
+
+        // An unreachable difficulty would otherwise search indefinitely.
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(solve_with_memory_cancellable(&data, mem, 256, &cancel), None);
+        let mut search: BumpSearch = BumpSearch::empty();
+        assert_eq!(
+            try_solve_in(&mut search, &data, mem, ALL_BUMPS, 256, &SolveLimits::UNLIMITED, Some(&cancel)),
+            Err(PackxError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_solve_with_scratch_matches_solve_one_bump() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        rng.fill_bytes(&mut pubkey);
+        let tables: Vec<_> = (0u8..=TEST_BUMP_TRIES).map(|bump| build_one_bump(&pubkey, bump)).collect();
+
+        let mut scratch = SolveScratch::new();
+        for _ in 0..4 {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            for (bump, table) in tables.iter().enumerate() {
+                let bump = bump as u8;
+                assert_eq!(
+                    solve_one_bump_with_scratch(&data, bump, table, TEST_DIFFICULTY, &mut scratch),
+                    solve_one_bump(&data, bump, table, TEST_DIFFICULTY)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpack_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut pubkey);
+        rng.fill_bytes(&mut data);
+
+        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(unpack(&pubkey, &solution), data);
+    }
+
+    #[test]
+    fn test_solver_reuse_matches_solve_with_memory() {
+        let (_, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut solver = Solver::new();
+        for _ in 0..4 {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            assert_eq!(solver.solve(&data, mem, TEST_DIFFICULTY), solve_with_memory(&data, mem, TEST_DIFFICULTY));
+        }
+    }
+
+    #[test]
+    fn test_solve_with_memory_is_deterministic() {
+        let (_, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        for difficulty in [0, TEST_DIFFICULTY, 4] {
+            let mut data = [0u8; 128];
+            rng.fill_bytes(&mut data);
+            let a = solve_with_memory(&data, mem, difficulty).expect("solve failed");
+            let b = solve_with_memory(&data, mem, difficulty).expect("solve failed");
+            assert_eq!(a.to_bytes(), b.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_solve_to_target() {
+        let (pubkey, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let any = solve_to_target(&data, mem, &[0xFF; 32]).expect("solve failed");
+        assert_eq!(Some(any), solve_with_memory(&data, mem, 0));
+
+        let mut target = [0xFF; 32];
+        target[0] = 0x0F;
+        let solution = solve_to_target(&data, mem, &target).expect("solve failed");
+        assert!(meets_target(&solution.solution_hash(), &target));
+        assert!(verify(pubkey, &data, &solution, 4));
+    }
+
+    #[test]
+    fn test_solve_with_memory_bumps() {
+        let (pubkey, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let full = solve_with_memory(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(solve_with_memory_bumps(&data, mem, TEST_DIFFICULTY, 0..=255), Some(full));
+
+        let skipped = solve_with_memory_bumps(&data, mem, TEST_DIFFICULTY, (0..=255).filter(|&b| b != full.bump))
+            .expect("solve failed");
+        assert_ne!(skipped.bump, full.bump);
+        assert!(verify(pubkey, &data, &skipped, TEST_DIFFICULTY));
+
+        let reversed = solve_with_memory_bumps(&data, mem, TEST_DIFFICULTY, (0..=255).rev()).expect("solve failed");
+        assert!(reversed.bump >= full.bump);
+
+        assert_eq!(solve_with_memory_bumps(&data, mem, TEST_DIFFICULTY, []), None);
+    }
+
+    #[test]
+    fn test_partial_memory_matches_full_memory() {
+        let (pubkey, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let full = solve_with_memory(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        let range = full.bump.saturating_sub(1)..=full.bump;
+        let partial = build_memory_range(pubkey, range.clone());
+        assert_eq!(partial.bumps(), range);
+        assert_eq!(partial.pubkey(), pubkey);
+        assert_eq!(partial.tables.len(), range.count());
+        assert!(partial.table(full.bump).is_some());
+        if full.bump < u8::MAX {
+            assert!(partial.table(full.bump + 1).is_none());
+        }
+        assert_eq!(solve_with_partial_memory(&data, &partial, TEST_DIFFICULTY), Some(full));
+    }
+
+    #[test]
+    fn test_partial_memory_merge() {
+        let part = |pubkey: u8, bumps: core::ops::RangeInclusive<u8>| {
+            let tables = bumps
+                .clone()
+                .map(|bump| {
+                    let mut table = alloc_table();
+                    table.present[0][0] = bump;
+                    table
+                })
+                .collect();
+            PartialSolverMemory { pubkey: [pubkey; 32], bumps, tables, algo: HashAlgo::Blake3 }
+        };
+
+        let merged = part(1, 128..=255).merge(part(1, 0..=127)).unwrap();
+        assert_eq!(merged.tables.len(), 256);
+        assert!(merged.tables.iter().enumerate().all(|(bump, table)| table.present[0][0] == bump as u8));
+        assert_eq!(merged.pubkey(), Some(&[1u8; 32]));
+
+        assert!(part(1, 0..=255).merge(part(1, core::ops::RangeInclusive::new(1, 0))).is_ok());
+        assert_eq!(part(1, 0..=127).merge(part(2, 128..=255)).err(), Some(PackxError::PubkeyMismatch));
+        assert_eq!(part(1, 0..=130).merge(part(1, 128..=255)).err(), Some(PackxError::BumpOverlap { bump: 128 }));
+        assert_eq!(part(1, 0..=126).merge(part(1, 128..=255)).err(), Some(PackxError::BumpGap { bump: 127 }));
+        assert_eq!(part(1, 1..=127).merge(part(1, 128..=254)).err(), Some(PackxError::BumpGap { bump: 0 }));
+    }
+
+    #[test]
+    fn test_solve_best_with_memory() {
+        let (pubkey, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let (best, difficulty) = solve_best_with_memory(&data, mem, 64).expect("solve failed");
+        assert_eq!(best.difficulty(), difficulty);
+        assert!(verify(pubkey, &data, &best, difficulty));
+
+        let first = solve_with_memory(&data, mem, 0).expect("solve failed");
+        assert!(difficulty >= first.difficulty());
+    }
+
+    #[test]
+    fn test_solutions_with_memory() {
+        let (pubkey, mem) = shared_memory();
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+
+        let found: Vec<Solution> = solutions_with_memory(&data, mem, TEST_DIFFICULTY).take(5).collect();
+        assert_eq!(found.len(), 5);
+        assert_eq!(Some(found[0]), solve_with_memory(&data, mem, TEST_DIFFICULTY));
+        for (i, solution) in found.iter().enumerate() {
+            assert!(verify(pubkey, &data, solution, TEST_DIFFICULTY));
+            assert!(found[..i].iter().all(|prev| prev != solution));
+        }
+    }
+
+    #[test]
+    fn test_packer_slice_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let mut pubkey = [0u8; 32];
+        rng.fill_bytes(&mut pubkey);
+        let packer = Packer::new(&pubkey, 0);
+
+        let mut data = vec![0u8; 300];
+        rng.fill_bytes(&mut data);
+        let solutions = packer.pack_slice(&data).expect("pack failed");
+        assert_eq!(solutions.len(), 3);
+        assert_eq!(unpack_slice(&pubkey, &solutions, data.len()), data);
+
+        let empty = packer.pack_slice(&[]).expect("pack failed");
+        assert!(empty.is_empty());
+        assert!(unpack_slice(&pubkey, &empty, 0).is_empty());
+    }
+
+    /// Memory where only seed 0 of every bump is present, reaching the given targets with nonce 0.
+    fn single_seed_memory(present: [u8; 32]) -> SolverMemory {
+        let tables = (0..256)
+            .map(|_| {
+                let mut table = Box::new(SeedTable {
+                    nonces: vec![[0u8; 256]; 256].into_boxed_slice(),
+                    present: vec![[0u8; 32]; 256].into_boxed_slice(),
+                });
+                table.present[0] = present;
+                table
+            })
+            .collect();
+        SolverMemory::new(tables, HashAlgo::Blake3)
+    }
+
+    #[test]
+    fn test_coverage_stats() {
+        let mut present = [0xFFu8; 32];
+        present[3] = 0b0000_0111;
+        let mut mem = single_seed_memory(present);
+        mem.tables[9].present[0][0] = 0;
+        mem.tables[9].present[1] = [0xFF; 32];
+
+        let coverage = mem.tables[0].coverage();
+        assert_eq!(coverage[0], 256 - 5);
+        assert!(coverage[1..].iter().all(|&c| c == 0));
+        assert_eq!(mem.tables[0].fully_covered_seeds(), 0);
+        assert_eq!(mem.tables[9].fully_covered_seeds(), 1);
+        assert_eq!(mem.min_coverage(), (0, 1, 0));
+
+        let (_, shared) = shared_memory();
+        let (bump, seed, count) = shared.min_coverage();
+        assert_eq!(shared.tables[bump as usize].coverage()[seed as usize], count);
+        assert!(shared.tables.iter().all(|t| t.coverage().iter().all(|&c| c >= count)));
+    }
+
+    #[test]
+    fn test_unreachable_targets() {
+        let mut present = [0xFFu8; 32];
+        present[0] &= !0b101; // targets 0 and 2 unreachable
+        let mem = single_seed_memory(present);
+
+        let mut data = [1u8; 128];
+        assert_eq!(unreachable_targets(&mem, &data), None);
+
+        data[5 * 8 + 3] = 0;
+        data[5 * 8 + 6] = 0;
+        data[9 * 8] = 2;
+        assert_eq!(unreachable_targets(&mem, &data), Some(vec![(5, 0), (9, 2)]));
+
+        let (_, shared) = shared_memory();
+        assert_eq!(unreachable_targets(shared, &[0x42; 128]), None);
+    }
+
+    #[test]
+    fn test_solver_memory_bytes_roundtrip() {
+        let (_, mem) = shared_memory();
+        let bytes = mem.to_bytes();
+        assert_eq!(bytes.len(), SOLVER_MEMORY_BYTES);
+        assert_eq!(&bytes[SEED_TABLE_BYTES..SEED_TABLE_BYTES + 256], &mem.tables[1].nonces[0][..]);
+
+        let loaded = SolverMemory::from_bytes(&bytes).unwrap();
+        for (a, b) in loaded.tables.iter().zip(mem.tables.iter()) {
+            assert_eq!(a.nonces, b.nonces);
+            assert_eq!(a.present, b.present);
+        }
+        assert_eq!(loaded.coverage_scores, mem.coverage_scores);
+        assert_eq!(loaded.algo, mem.algo);
+
+        let err = SolverMemory::from_bytes(&bytes[1..]).err();
+        assert_eq!(err, Some(PackxError::BadLength { expected: SOLVER_MEMORY_BYTES, got: SOLVER_MEMORY_BYTES - 1 }));
+    }
+
+    #[test]
+    fn test_nonce_for_matches_h0() {
+        let (pubkey, mem) = shared_memory();
+        let table = &mem.tables[5];
+        for seed in [0u8, 99, 255] {
+            let reached: Vec<u8> = (0u8..=u8::MAX).map(|nonce| HashParams::DEFAULT.h0(pubkey, 5, seed, nonce)).collect();
+            for target in 0u8..=u8::MAX {
+                let first = reached.iter().position(|&t| t == target).map(|nonce| nonce as u8);
+                assert_eq!(table.nonce_for(seed, target), first);
+                assert_eq!(table.can_reach(seed, target), first.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_nonce_selection() {
+        let (pubkey, mem) = shared_memory();
+        let random = build_one_bump_with_selection(pubkey, 0, NonceSelection::Random(9));
+        assert_eq!(random.present, mem.tables[0].present);
+        assert_ne!(random.nonces, mem.tables[0].nonces);
+        assert_eq!(build_one_bump_with_selection(pubkey, 0, NonceSelection::Random(9)).nonces, random.nonces);
+        assert_ne!(build_one_bump_with_selection(pubkey, 0, NonceSelection::Random(10)).nonces, random.nonces);
+        assert_eq!(build_one_bump_with_selection(pubkey, 0, NonceSelection::First).nonces, mem.tables[0].nonces);
+
+        // A single row rebuilds without the rows before it.
+        let mut hasher = BumpHasher::new(pubkey, 0, &HashParams::DEFAULT);
+        let (mut present_row, mut nonces_row) = ([0u8; 32], [0u8; 256]);
+        fill_row_random(&mut hasher, 200, &mut present_row, &mut nonces_row, selection_key(pubkey, 0, 9));
+        assert_eq!((present_row, nonces_row), (random.present[200], random.nonces[200]));
+
+        for seed in [0u8, 128, 255] {
+            for target in 0u8..=u8::MAX {
+                if let Some(nonce) = random.nonce_for(seed, target) {
+                    assert_eq!(HashParams::DEFAULT.h0(pubkey, 0, seed, nonce), target);
+                }
+            }
+        }
+
+        let data = [0x66u8; 128];
+        let solution = (0u8..=TEST_BUMP_TRIES)
+            .find_map(|bump| {
+                let table = build_one_bump_with_selection(pubkey, bump, NonceSelection::Random(9));
+                solve_one_bump(&data, bump, &table, TEST_DIFFICULTY)
+            })
+            .expect("solve failed");
+        assert_eq!(unpack(pubkey, &solution), data);
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_coverage_memory() {
+        let (pubkey, mem) = shared_memory();
+        assert_eq!(build_coverage(pubkey, 3).present, mem.tables[3].present);
+
+        let coverage = mem.coverage_memory();
+        let data = [0x42u8; 128];
+        assert!(coverage.can_pack(&data));
+        assert_eq!(coverage.unreachable_targets(&data), None);
+
+        let mut present = [0xFFu8; 32];
+        present[0] &= !0b101;
+        let blocked = single_seed_memory(present);
+        let mut bad = [1u8; 128];
+        bad[9 * 8] = 2;
+        let coverage = blocked.coverage_memory();
+        assert!(!coverage.can_pack(&bad));
+        assert_eq!(coverage.unreachable_targets(&bad), unreachable_targets(&blocked, &bad));
+
+        let mut file = vec![1u8; 3 * 128 + 5];
+        file[128 + 9 * 8] = 2;
+        file[3 * 128] = 0;
+        assert_eq!(coverage.unpackable_chunks(&file), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_solve_with_memory_by_coverage() {
+        let (pubkey, mem) = shared_memory();
+        let order = mem.bumps_by_coverage();
+        assert_eq!(order.len(), 256);
+        assert!(order.windows(2).all(|w| {
+            let (a, b) = (mem.coverage_scores[w[0] as usize], mem.coverage_scores[w[1] as usize]);
+            a > b || (a == b && w[0] < w[1])
+        }));
+        assert_eq!(mem.coverage_scores[order[0] as usize], mem.tables[order[0] as usize].coverage_score());
+
+        let data = [0x9Du8; 128];
+        let solution = solve_with_memory_by_coverage(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+        let first_covering = order.iter().find(|&&b| combination_count(&data, &mem.tables[b as usize]) > 0);
+        assert_eq!(Some(&solution.bump), first_covering);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_solve_with_memory_stats() {
+        let (_, mem) = shared_memory();
+        let data = [0xC4u8; 128];
+
+        let (solution, stats) = solve_with_memory_stats(&data, mem, 4).expect("solve failed");
+        assert_eq!(Some(solution), solve_with_memory(&data, mem, 4));
+        assert_eq!(stats.bumps_tried, solution.bump as usize + 1);
+        assert!(stats.combinations_tried >= 1);
+        assert!(stats.elapsed > std::time::Duration::ZERO);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_solver_memory() {
+        use zeroize::Zeroize;
+
+        let mut mem = single_seed_memory([0xFF; 32]);
+        mem.tables[3].nonces[0][9] = 42;
+        mem.zeroize();
+        assert!(mem.tables.iter().all(|t| t.present.iter().all(|p| *p == [0; 32])));
+        assert!(mem.tables.iter().all(|t| t.nonces.iter().all(|n| *n == [0; 256])));
+        assert!(mem.coverage_scores.iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_build_memory_into_matches_fresh_build() {
+        let (pubkey, fresh) = shared_memory();
+
+        // Start from junk so stale bytes would show up in the comparison.
+        let mut mem = single_seed_memory([0xA5; 32]);
+        for table in mem.tables.iter_mut() {
+            table.nonces.iter_mut().for_each(|row| *row = [0xEE; 256]);
+        }
+        build_memory_into(pubkey, &mut mem);
+
+        assert_eq!(mem.algo, fresh.algo);
+        assert_eq!(mem.pubkey(), Some(pubkey));
+        assert_eq!(mem.coverage_scores, fresh.coverage_scores);
+        for (a, b) in mem.tables.iter().zip(fresh.tables.iter()) {
+            assert_eq!(a.nonces, b.nonces);
+            assert_eq!(a.present, b.present);
+        }
+    }
+
+    #[test]
+    fn test_build_memory_with_progress() {
+        let (pubkey, expected) = shared_memory();
+        let mut seen = Vec::new();
+        let mem = build_memory_with_progress(pubkey, |bump| seen.push(bump));
+
+        assert_eq!(seen, (0u8..=u8::MAX).collect::<Vec<_>>());
+        assert_eq!(mem.coverage_scores, expected.coverage_scores);
+        assert_eq!(mem.tables[255].nonces, expected.tables[255].nonces);
+    }
+
+    #[test]
+    fn test_try_solve_reports_failure_reason() {
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1; // target 0 unreachable
+        let mem = single_seed_memory(present);
+
+        let mut data = [1u8; 128];
+        data[5 * 8 + 3] = 0;
+        assert_eq!(try_solve_with_memory(&data, &mem, 0), Err(PackxError::NoCoverage { group: 5 }));
+        assert_eq!(solve_with_memory(&data, &mem, 0), None);
+
+        let data = [1u8; 128];
+        assert_eq!(try_solve_with_memory(&data, &mem, 256), Err(PackxError::DifficultyUnreached));
+        assert!(try_solve_with_memory(&data, &mem, 0).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("packx-{}-{name}", std::process::id()))
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_memory_cache_lru() {
+        use std::sync::Arc;
+
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<MemoryCache>();
+
+        let cache = MemoryCache::new(2);
+        let builds = core::cell::Cell::new(0);
+        let get = |key: u8| {
+            cache.get_or_build_with(&[key; 32], || {
+                builds.set(builds.get() + 1);
+                single_seed_memory([key; 32])
+            })
+        };
+
+        let a = get(1);
+        assert!(Arc::ptr_eq(&a, &get(1)));
+        get(2);
+        get(1); // 2 is now least recently used
+        let c = get(3);
+        assert_eq!(builds.get(), 3);
+        get(1);
+        assert_eq!(builds.get(), 3);
+        get(2);
+        assert_eq!(builds.get(), 4);
+        assert_eq!(c.tables[0].present[0], [3; 32]); // evicted, but still valid for holders
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&[3; 32]).is_none());
+        cache.clear();
+        assert!(cache.is_empty());
+        let uncached = MemoryCache::new(0);
+        uncached.get_or_build_with(&[1; 32], || single_seed_memory([0; 32]));
+        assert!(uncached.is_empty());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_solve_with_memory_async() {
+        use std::future::Future;
+        use std::sync::Arc;
+        use std::task::Poll;
+
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let mem = Arc::new(single_seed_memory([0xFF; 32]));
+        let data = [0x2Bu8; 128];
+        let solution = rt.block_on(solve_with_memory_async(&data, Arc::clone(&mem), 0));
+        assert_eq!(solution, solve_with_memory(&data, &mem, 0));
+
+        // Every seed covers everything, so an unreachable difficulty would search forever;
+        // dropping the future after it starts must stop the blocking task.
+        let tables = (0..256).map(|_| {
+            let mut table = alloc_table();
+            table.present.fill([0xFF; 32]);
+            table
+        });
+        let endless = Arc::new(SolverMemory::new(tables.collect(), HashAlgo::Blake3));
+        rt.block_on(async {
+            let mut fut = Box::pin(solve_with_memory_async(&data, endless, MAX_DIFFICULTY));
+            let first = std::future::poll_fn(|cx| Poll::Ready(fut.as_mut().poll(cx))).await;
+            assert!(first.is_pending());
+        });
+        let start = std::time::Instant::now();
+        rt.shutdown_timeout(std::time::Duration::from_secs(30));
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_solve_async_after_runtime_shutdown() {
+        use std::future::Future;
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Waker};
+
+        // A runtime that has shut down cancels blocking tasks instead of running them.
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let handle = rt.handle().clone();
+        rt.shutdown_timeout(std::time::Duration::from_secs(10));
+
+        let _entered = handle.enter();
+        let mem = Arc::new(single_seed_memory([0xFF; 32]));
+        let mut fut = Box::pin(solve_with_memory_async(&[0x2B; 128], mem, 0));
+        assert_eq!(fut.as_mut().poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(None));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_save_load_memory_roundtrip() {
+        let (pubkey, mem) = shared_memory();
+        let path = temp_path("save-load.bin");
+        mem.save_to_path(pubkey, &path).expect("save failed");
+
+        let loaded = SolverMemory::load_from_path(&path, pubkey).expect("load failed");
+        for (a, b) in mem.tables.iter().zip(loaded.tables.iter()) {
+            assert_eq!(a.nonces, b.nonces);
+            assert_eq!(a.present, b.present);
+        }
+        assert_eq!(loaded.pubkey(), Some(pubkey));
+        loaded.prefault();
+
+        let mut other = *pubkey;
+        other[0] ^= 1;
+        let err = SolverMemory::load_from_path(&path, &other).err().expect("pubkey mismatch should fail");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let err = mem.save_to_path(&other, &path).expect_err("saving under another pubkey should fail");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        #[cfg(feature = "mmap")]
+        {
+            let mapped = unsafe { SolverMemory::mmap_from_path(&path, pubkey) }.expect("mmap failed");
+            mapped.prefault();
+            let mut data = [0u8; 128];
+            rand::thread_rng().fill_bytes(&mut data);
+            assert_eq!(mapped.solve(&data, TEST_DIFFICULTY), solve_with_memory(&data, mem, TEST_DIFFICULTY));
+        }
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_bump_hasher_matches_h0() {
+        let pubkey = [0xA5u8; 32];
+        for params in [
+            HashParams::DEFAULT,
+            HashParams::DEFAULT.with_offset(31),
+            HashParams::DEFAULT.with_domain(b"domain"),
+        ] {
+            let mut hasher = BumpHasher::new(&pubkey, 200, &params);
+            for (seed, nonce) in [(0, 0), (1, 255), (77, 3), (255, 128)] {
+                assert_eq!(hasher.h0(seed, nonce), params.h0(&pubkey, 200, seed, nonce));
+            }
+        }
+    }
+
+    #[test]
+    fn test_difficulty_profile() {
+        let (pubkey, mem) = shared_memory();
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 13 % 251) as u8).collect();
+
+        let profile = difficulty_profile(pubkey, &data, 3);
+        let expected: Vec<u32> = [0usize, 2, 5]
+            .iter()
+            .map(|&chunk| {
+                let block = pad_chunk(&data[chunk * 128..data.len().min(chunk * 128 + 128)]);
+                solve_with_memory(&block, mem, 0).expect("solve failed").difficulty()
+            })
+            .collect();
+        assert_eq!(profile, expected);
+
+        assert_eq!(difficulty_profile(pubkey, &data[..200], 10).len(), 2);
+        assert!(difficulty_profile(pubkey, &[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_v2_bump_covers_every_target() {
+        let pubkey = [0x21u8; 32];
+        let table = build_one_bump_v2(&pubkey, 9);
+        assert!(table.present.iter().all(|present| *present == [0xFF; 32]));
+
+        let mut rng = rand::thread_rng();
+        let mut data = [0u8; 128];
+        rng.fill_bytes(&mut data);
+        let solution = solve_one_bump_v2(&data, 9, &table, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(unpack_v2(&pubkey, &solution), data);
+        assert!(verify_v2(&pubkey, &data, &solution, TEST_DIFFICULTY));
+        assert!(!verify_v2(&[0x22u8; 32], &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_solution64_roundtrip() {
+        let (pubkey, mem) = shared_memory();
+        let mut data = [0u8; 64];
+        data[..10].copy_from_slice(b"short note");
+
+        let solution = solve_with_memory64(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(Some(solution), solve_with_memory_groups::<8, 64>(&data, mem, TEST_DIFFICULTY).ok());
+        assert!(verify64(pubkey, &data, &solution, TEST_DIFFICULTY));
+        assert!(!verify64(&[0x22; 32], &data, &solution, TEST_DIFFICULTY));
+
+        let bytes = solution.to_bytes();
+        assert_eq!(bytes.len(), SOLUTION64_SIZE);
+        assert_eq!(Solution64::from_bytes(&bytes), solution);
+        assert_eq!(unpack(pubkey, &Solution64::from_bytes(&bytes)), data);
+    }
+
+    #[test]
+    fn test_solution_g4_roundtrip() {
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+
+        let solution = solve_with_memory_g4(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(SolutionG4::GROUP_BYTES, 4);
+        assert_eq!(unpack(pubkey, &solution), data);
+        assert!(verify_g4(pubkey, &data, &solution, TEST_DIFFICULTY));
+        assert!(!verify_g4(&[0x22; 32], &data, &solution, TEST_DIFFICULTY));
+        assert!(verify_reconstruction(pubkey, &data, &solution));
+
+        let bytes = solution.to_bytes();
+        assert_eq!(bytes.len(), SOLUTION_G4_SIZE);
+        assert_eq!(SolutionG4::from_bytes(&bytes), solution);
+
+        // A nonce that hashes to a different byte, so the corruption is always detected.
+        let mut corrupted = solution;
+        corrupted.nonces[42] = (0u8..=u8::MAX)
+            .find(|&nonce| HashParams::DEFAULT.h0(pubkey, solution.bump, solution.seeds[10], nonce) != data[42])
+            .unwrap();
+        let err = unpack_checked(pubkey, &data, &corrupted).unwrap_err();
+        assert_eq!((err.index, err.group), (42, 10));
+    }
+
+    #[test]
+    fn test_g4_covers_what_8_byte_groups_cannot() {
+        // Seed 0 reaches targets 1..=4 and seed 1 reaches 5..=8, so no seed covers a group of 8.
+        let mut mem = single_seed_memory([0b0001_1110, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        for table in mem.tables.iter_mut() {
+            table.present[1][0] = 0b1110_0000;
+            table.present[1][1] = 0b0000_0001;
+        }
+        let data: [u8; 128] = core::array::from_fn(|i| (i % 8) as u8 + 1);
+
+        assert_eq!(solve_with_memory(&data, &mem, 0), None);
+        let solution = solve_with_memory_g4(&data, &mem, 0).expect("4-byte groups are covered");
+        assert!(solution.seeds.iter().enumerate().all(|(g, &seed)| seed == (g % 2) as u8));
+    }
+
+    #[test]
+    fn test_solve_with_memory_groups() {
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 64] = core::array::from_fn(|i| (i * 29) as u8);
+        let solution = solve_with_memory_groups::<8, 64>(&data, mem, TEST_DIFFICULTY).expect("solve failed");
+        assert_eq!(unpack(pubkey, &solution), data);
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+
+        // The default shape goes through the same search as solve_with_memory.
+        let data128 = [0x3Cu8; 128];
+        assert_eq!(
+            solve_with_memory_groups::<16, 128>(&data128, mem, TEST_DIFFICULTY).ok(),
+            solve_with_memory(&data128, mem, TEST_DIFFICULTY)
+        );
+    }
+
+    #[test]
+    fn test_mixed_radix_seek_matches_sequential() {
+        let radices = [2, 3, 1, 4];
+        let sequential: Vec<[usize; 4]> = MixedRadixCounter::new(radices).unwrap().collect();
+        assert_eq!(MixedRadixCounter::new(radices).unwrap().total(), 24);
+        assert_eq!(sequential.len(), 24);
+
+        for start in 0..24 {
+            let mut iter = MixedRadixCounter::new(radices).unwrap();
+            iter.seek(start as u128);
+            assert_eq!(iter.collect::<Vec<_>>(), sequential[start..]);
+        }
+
+        let mut iter = MixedRadixCounter::new(radices).unwrap();
+        iter.seek(24);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(MixedRadixCounter::new([256; 16]).unwrap().total(), u128::MAX);
+        assert_eq!(MixedRadixCounter::new([256; 15]).unwrap().total(), 1u128 << 120);
+    }
+
+    #[test]
+    fn test_combination_count() {
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        let mem = single_seed_memory(present);
+        assert_eq!(combination_count(&[1u8; 128], &mem.tables[0]), 1);
+        assert_eq!(combination_count(&[0u8; 128], &mem.tables[0]), 0);
+
+        let (_, shared) = shared_memory();
+        let data = [0x77u8; 128];
+        let count = combination_count(&data, &shared.tables[4]);
+        let search = BumpSearch::<16, 128>::new(&data, 4, shared.tables[4].view()).unwrap();
+        let product = search.cands.iter().fold(1u128, |acc, c| acc.saturating_mul(c.len() as u128));
+        assert_eq!(count, product);
+
+        let mut tail = search.combinations_from(count.saturating_sub(1)).unwrap();
+        assert!(tail.next().is_some());
+        assert!(count == u128::MAX || tail.next().is_none());
+    }
+
+    #[test]
+    fn test_hex_and_base58_roundtrip() {
+        let solution = Solution { bump: 0, seeds: [0xAB; 16], nonces: core::array::from_fn(|i| i as u8) };
+
+        let hex = solution.to_hex();
+        assert_eq!(hex.len(), 290);
+        assert!(hex.starts_with("00abab"));
+        assert_eq!(Solution::from_hex(&hex), Ok(solution));
+        assert_eq!(Solution::from_hex(&hex.to_uppercase()), Ok(solution));
+
+        #[cfg(feature = "base58")]
+        {
+            let b58 = solution.to_base58();
+            assert!(b58.starts_with('1')); // leading zero byte
+            assert_eq!(Solution::from_base58(&b58), Ok(solution));
+        }
+    }
+
+    #[test]
+    fn test_hex_and_base58_reject_bad_input() {
+        let hex = Solution::default().to_hex();
+        assert_eq!(Solution::from_hex(&hex[1..]), Err(PackxError::BadLength { expected: 290, got: 289 }));
+
+        let mut bad = hex.clone();
+        bad.replace_range(7..8, "g");
+        assert_eq!(Solution::from_hex(&bad), Err(PackxError::InvalidCharacter { index: 7, character: 'g' }));
+
+        let mut bad = hex;
+        bad.replace_range(288..289, "é");
+        assert_eq!(
+            Solution::from_hex(&bad[..290]),
+            Err(PackxError::InvalidCharacter { index: 288, character: 'é' })
+        );
+
+        #[cfg(feature = "base58")]
+        {
+            assert_eq!(
+                Solution::from_base58("11O1"),
+                Err(PackxError::InvalidCharacter { index: 2, character: 'O' })
+            );
+            assert_eq!(Solution::from_base58("2g"), Err(PackxError::BadLength { expected: SOLUTION_SIZE, got: 1 }));
+        }
+    }
+
+    #[test]
+    fn test_group_candidates_reconstruct_group() {
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i * 11) as u8);
+        let table = &mem.tables[17];
+
+        let cands = group_candidates(&data, 3, table);
+        assert!(!cands.is_empty());
+        assert!(cands.windows(2).all(|w| w[0].seed < w[1].seed));
+        for cand in &cands {
+            for (i, &nonce) in cand.nonces8.iter().enumerate() {
+                assert_eq!(HashParams::DEFAULT.h0(pubkey, 17, cand.seed, nonce), data[24 + i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_one_bump_heuristic() {
+        let (pubkey, mem) = shared_memory();
+        let data = [0x2Au8; 128];
+        let table = &mem.tables[0];
+
+        let a = solve_one_bump_heuristic(&data, 0, table, 4, 1).expect("solve failed");
+        assert!(verify(pubkey, &data, &a, 4));
+        assert_eq!(solve_one_bump_heuristic(&data, 0, table, 4, 1), Some(a));
+
+        // Different seeds walk different orders but stay equally complete.
+        let b = solve_one_bump_heuristic(&data, 0, table, 4, 2).expect("solve failed");
+        assert!(verify(pubkey, &data, &b, 4));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_difficulty_mode_roundtrip() {
+        let (pubkey, mem) = shared_memory();
+        let data = [0x6Bu8; 128];
+
+        for mode in [DifficultyMode::SolutionOnly, DifficultyMode::DataBound] {
+            let solution = solve_with_memory_mode(pubkey, &data, mem, 6, mode).expect("solve failed");
+            assert!(verify_with_mode(pubkey, &data, &solution, 6, mode));
+            assert!(mode.difficulty(pubkey, &data, &solution) >= 6);
+
+            let table = &mem.tables[solution.bump as usize];
+            let one = solve_one_bump_with_mode(pubkey, &data, solution.bump, table, 6, mode);
+            assert_eq!(one, Some(solution));
+        }
+
+        // SolutionOnly is the existing behaviour.
+        let plain = solve_with_memory(&data, mem, 6).unwrap();
+        assert_eq!(solve_with_memory_mode(pubkey, &data, mem, 6, DifficultyMode::SolutionOnly), Some(plain));
+        assert_eq!(DifficultyMode::SolutionOnly.difficulty(pubkey, &data, &plain), plain.difficulty());
+
+        // The data-bound hash changes with the pubkey even though the solution bytes don't.
+        let bound = DifficultyMode::DataBound;
+        assert_ne!(bound.hash(pubkey, &data, &plain), bound.hash(&[0u8; 32], &data, &plain));
+    }
+
+    #[test]
+    fn test_pack_with_memory_reports_hash() {
+        let (pubkey, mem) = shared_memory();
+        let data = [0x0Fu8; 128];
+
+        let result = pack_with_memory(&data, mem, 5).expect("pack failed");
+        assert_eq!(Some(result.solution), solve_with_memory(&data, mem, 5));
+        assert_eq!(result.bump, result.solution.bump);
+        assert_eq!(result.hash, result.solution.solution_hash());
+        assert_eq!(result.difficulty, result.solution.difficulty());
+        assert!(result.difficulty >= 5);
+        assert!(verify(pubkey, &data, &result.solution, result.difficulty));
+    }
+
+    #[test]
+    fn test_max_solvable_difficulty_matches_limited_solve() {
+        let (_, mem) = shared_memory();
+        let data = [0x5Au8; 128];
+        let limits = SolveLimits::max_iterations(32);
+
+        let best = max_solvable_difficulty_with_memory(&data, mem, 32);
+        assert!(solve_with_memory_limited(&data, mem, best, &limits).is_some());
+        assert!(solve_with_memory_limited(&data, mem, best + 1, &limits).is_none());
+        assert!(max_solvable_difficulty_with_memory(&data, mem, 256) >= best);
+    }
+
+    #[test]
+    fn test_distinct_seeds_constraint() {
+        let (pubkey, mem) = shared_memory();
+        let distinct = SolveConstraints { distinct_seeds: true };
+        let mut data = [0u8; 128];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(37);
+        }
+
+        let solution = solve_with_memory_constrained(&data, mem, TEST_DIFFICULTY, &distinct).expect("solve failed");
+        let mut seeds = solution.seeds.to_vec();
+        seeds.sort_unstable();
+        seeds.dedup();
+        assert_eq!(seeds.len(), 16);
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+        assert_eq!(
+            solve_with_memory_constrained(&data, mem, TEST_DIFFICULTY, &SolveConstraints::NONE),
+            solve_with_memory(&data, mem, TEST_DIFFICULTY)
+        );
+
+        // Identical groups share one candidate list; fewer than 16 candidates can't be distinct.
+        let same: [u8; 128] = core::array::from_fn(|i| (i % 8) as u8 * 31);
+        let bump = ALL_BUMPS
+            .into_iter()
+            .find(|&bump| (1..16).contains(&group_candidates(&same, 0, &mem.tables[bump as usize]).len()))
+            .expect("no bump with few candidates");
+        let table = &mem.tables[bump as usize];
+        assert!(solve_one_bump(&same, bump, table, 0).is_some());
+        assert_eq!(solve_one_bump_constrained(&same, bump, table, 0, &distinct), None);
+    }
+
+    #[test]
+    fn test_solve_in_range() {
+        let (pubkey, mem) = shared_memory();
+        let data = [0x71u8; 128];
+
+        for (min, max) in [(0, 0), (3, 3), (2, 5)] {
+            let solution = solve_in_range(&data, mem, min, max).expect("solve failed");
+            assert!((min..=max).contains(&solution.difficulty()));
+            assert!(verify(pubkey, &data, &solution, min));
+        }
+
+        // An open-ended range matches the threshold solve; capping below it skips that hit.
+        let first = solve_with_memory(&data, mem, 2).unwrap();
+        assert_eq!(solve_in_range(&data, mem, 2, MAX_DIFFICULTY), Some(first));
+        assert_ne!(solve_in_range(&data, mem, 0, 1), Some(first));
+        assert_eq!(solve_in_range(&data, mem, 4, 3), None);
+    }
+
+    #[test]
+    fn test_resume_solve_matches_unbounded() {
+        let (_, mem) = shared_memory();
+        let data = [0x27u8; 128];
+        let expected = solve_with_memory(&data, mem, 10).expect("solve failed");
+
+        let mut cursor = SolveCursor::START;
+        let mut pauses = 0;
+        let found = loop {
+            match resume_solve(&data, mem, 10, cursor, 16) {
+                SolveOutcome::Found(solution) => break solution,
+                SolveOutcome::Paused(next) => {
+                    assert!((next.bump, next.index) > (cursor.bump, cursor.index));
+                    cursor = SolveCursor::from_bytes(&next.to_bytes());
+                    pauses += 1;
+                }
+                SolveOutcome::Exhausted => panic!("search exhausted"),
+            }
+        };
+        assert_eq!(found, expected);
+        assert!(pauses > 0);
+
+        assert_eq!(resume_solve(&data, mem, 10, cursor, 0), SolveOutcome::Paused(cursor));
+        let past_end = SolveCursor { bump: 255, index: u128::MAX };
+        assert_eq!(resume_solve(&data, mem, 0, past_end, 16), SolveOutcome::Exhausted);
+    }
+
+    #[cfg(feature = "solana")]
+    #[test]
+    fn test_verify_bump_canonical() {
+        use solana_program::pubkey::Pubkey;
+
+        let program_id = Pubkey::new_from_array([9; 32]);
+        let seeds: &[&[u8]] = &[b"packx", &[7; 32]];
+        let (_, canonical) = Pubkey::find_program_address(seeds, &program_id);
+
+        assert!(verify_bump_canonical(&program_id, seeds, canonical));
+        assert!(!verify_bump_canonical(&program_id, seeds, canonical.wrapping_sub(1)));
+        let too_long: &[&[u8]] = &[&[0; 33]];
+        assert!(!verify_bump_canonical(&program_id, too_long, canonical));
+    }
+
+    #[test]
+    fn test_solve_one_bump_nostd_matches_vec() {
+        let (_, mem) = shared_memory();
+        let mut scratch = vec![SeedCandidate { seed: 0, nonces8: [0; 8] }; NOSTD_SCRATCH_LEN];
+        for (bump, fill) in [(0u8, 0x13u8), (1, 0xA0), (77, 0x5E)] {
+            let data = [fill; 128];
+            let table = &mem.tables[bump as usize];
+            for difficulty in [0, 6] {
+                assert_eq!(
+                    solve_one_bump_nostd(&data, bump, table.view(), difficulty, &mut scratch),
+                    solve_one_bump(&data, bump, table, difficulty)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_covering_seeds_and_present_targets() {
+        let (_, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        for g in [0, 7, 15] {
+            let bits = covering_seeds(&data, g, &mem.tables[4]);
+            let seeds: Vec<u8> = present_targets(&bits).collect();
+            let expected: Vec<u8> = group_candidates(&data, g, &mem.tables[4]).iter().map(|c| c.seed).collect();
+            assert_eq!(seeds, expected);
+        }
+
+        let mut bits = [0u8; 32];
+        bits[0] = 0b1000_0001;
+        bits[31] = 0x80;
+        assert_eq!(present_targets(&bits).collect::<Vec<_>>(), vec![0, 7, 255]);
+        assert_eq!(present_targets(&[0xFF; 32]).count(), 256);
+        assert_eq!(present_targets(&[0; 32]).next(), None);
+
+        let table = &mem.tables[0];
+        for seed in [0, 100, 255] {
+            let naive: Vec<u8> = (0..=255).filter(|&t| bit_test(&table.present[seed], t)).collect();
+            assert_eq!(present_targets(&table.present[seed]).collect::<Vec<_>>(), naive);
+            assert_eq!(naive.len(), table.coverage()[seed] as usize);
+        }
+    }
+
+    #[test]
+    fn test_solve_one_bump_ordered() {
+        let (_, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let bump = solve_with_memory(&data, mem, 0).unwrap().bump;
+        let table = &mem.tables[bump as usize];
+
+        let plain = solve_one_bump(&data, bump, table, 4);
+        assert_eq!(solve_one_bump_ordered(&data, bump, table, 4, OrderingStrategy::default()), plain);
+        for ordering in [OrderingStrategy::MostFirst, OrderingStrategy::GroupIndex] {
+            let solution = solve_one_bump_ordered(&data, bump, table, 4, ordering).unwrap();
+            assert!(verify(&[7u8; 32], &data, &solution, 4));
+        }
+    }
+
+    #[test]
+    fn test_solve_with_deadline() {
+        use std::time::{Duration, Instant};
 
-    fn solve_lightweight(pubkey: &[u8; 32], data: &[u8; 128], difficulty: u32) -> Option<Solution> {
-        for bump in 0u8..=TEST_BUMP_TRIES {
-            let table = build_one_bump(pubkey, bump);
-            if let Some(solution) = solve_one_bump(data, bump, &table, difficulty) {
-                return Some(solution);
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let far = Instant::now() + Duration::from_secs(3600);
+        assert_eq!(solve_with_deadline(&data, mem, 4, far, false), solve_with_memory(&data, mem, 4));
+
+        // Out of reach, so only the deadline ends the search.
+        let start = Instant::now();
+        let soon = start + Duration::from_millis(50);
+        assert_eq!(solve_with_deadline(&data, mem, MAX_DIFFICULTY, soon, false), None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let best = solve_with_deadline(&data, mem, MAX_DIFFICULTY, Instant::now() + Duration::from_millis(50), true);
+        assert!(verify(pubkey, &data, &best.unwrap(), 0));
+
+        let past = Instant::now() - Duration::from_millis(1);
+        assert_eq!(solve_with_deadline(&data, mem, 0, past, true), None);
+
+        // One combination per bump: every bump is exhausted long before the deadline.
+        let sparse = single_seed_memory([0xFF; 32]);
+        assert!(solve_with_memory(&data, &sparse, 0).is_some());
+        assert_eq!(solve_with_deadline(&data, &sparse, MAX_DIFFICULTY, far, true), None);
+    }
+
+    #[test]
+    fn test_memory_pubkey() {
+        let (pubkey, mem) = shared_memory();
+        assert_eq!(mem.pubkey(), Some(pubkey));
+
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        assert_eq!(solve_with_memory_checked(pubkey, &data, mem, 0).ok(), solve_with_memory(&data, mem, 0));
+        assert_eq!(solve_with_memory_checked(&[8u8; 32], &data, mem, 0), Err(PackxError::PubkeyMismatch));
+
+        // Without a recorded pubkey there is nothing to check against.
+        let anonymous = single_seed_memory([0xFF; 32]);
+        assert_eq!(anonymous.pubkey(), None);
+        assert!(solve_with_memory_checked(&[8u8; 32], &[0u8; 128], &anonymous, 0).is_ok());
+        assert_eq!(anonymous.with_pubkey(&[9u8; 32]).pubkey(), Some(&[9u8; 32]));
+    }
+
+    #[test]
+    fn test_solve_masked() {
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let all = [0xFF; 16];
+        assert_eq!(solve_masked(&data, all, mem, 4), solve_with_memory(&data, mem, 4));
+        assert!(verify_masked(pubkey, &data, &all, &solve_masked(&data, all, mem, 4).unwrap(), 4));
+
+        // Even bytes only; the odd ones come back as whatever nonce 0 hashes to.
+        let mask = [0x55; 16];
+        let solution = solve_masked(&data, mask, mem, 4).unwrap();
+        assert!(verify_masked(pubkey, &data, &mask, &solution, 4));
+        let unpacked = solution.unpack(pubkey);
+        assert!((0..128).step_by(2).all(|i| unpacked[i] == data[i]));
+        assert!(solution.nonces.iter().skip(1).step_by(2).all(|&nonce| nonce == 0));
+
+        let mut wrong = data;
+        wrong[2] ^= 1;
+        assert!(!verify_masked(pubkey, &wrong, &mask, &solution, 4));
+        wrong = data;
+        wrong[3] ^= 1;
+        assert!(verify_masked(pubkey, &wrong, &mask, &solution, 4));
+
+        // Target 0 is unreachable, but masking out the zero bytes leaves only reachable ones.
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        let mem = single_seed_memory(present);
+        let mut data = [0u8; 128];
+        data[5] = 9;
+        assert_eq!(solve_with_memory(&data, &mem, 0), None);
+        let mut mask = [0u8; 16];
+        mask[0] = 1 << 5;
+        assert_eq!(solve_masked(&data, mask, &mem, 0).map(|s| s.seeds), Some([0; 16]));
+        assert_eq!(solve_masked(&data, [0xFF; 16], &mem, 0), None);
+    }
+
+    #[test]
+    fn test_compact_roundtrip() {
+        let (pubkey, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let solution = solve_with_memory(&data, mem, 4).unwrap();
+        let compact = solution.to_compact();
+        assert_eq!(compact[0], solution.bump);
+        assert_eq!(compact[1..], solution.seeds);
+        assert_eq!(Solution::from_compact(pubkey, &data, &compact), Some(solution));
+
+        // A seed that can't reach the data has no nonces to recover.
+        let (bump, seed, _) = mem.min_coverage();
+        let target = (0..=255u8).find(|&t| !bit_test(&mem.tables[bump as usize].present[seed as usize], t)).unwrap();
+        let mut compact = [seed; SOLUTION_COMPACT_SIZE];
+        compact[0] = bump;
+        assert_eq!(Solution::from_compact(pubkey, &[target; 128], &compact), None);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_counters() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let (_, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let solution = solve_with_memory(&data, mem, 4).unwrap();
+            assert!(solve_one_bump(&[0u8; 128], 0, &single_seed_memory([0; 32]).tables[0], 0).is_none());
+            assert!(solve_one_bump(&data, solution.bump, &mem.tables[solution.bump as usize], 4).is_some());
+            build_memory_range(&[1; 32], 0..=1);
+        });
+
+        let counters: std::collections::HashMap<String, u64> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| match value {
+                DebugValue::Counter(n) => (key.key().name().to_string(), n),
+                other => panic!("unexpected metric {other:?}"),
+            })
+            .collect();
+        assert_eq!(counters[SOLVES_ATTEMPTED], 3);
+        assert_eq!(counters[SOLVES_SUCCEEDED], 2);
+        assert!(counters[COMBINATIONS_TRIED] >= 2);
+        assert_eq!(counters[MEMORIES_BUILT], 1);
+    }
+
+    #[test]
+    fn test_solve_shared_across_threads() {
+        use std::sync::Arc;
+
+        let (pubkey, mem) = shared_memory();
+        let shared = Arc::new(single_seed_memory([0xFF; 32]));
+        let handles: Vec<_> = (0..4u8)
+            .map(|i| {
+                let mem = Arc::clone(&shared);
+                std::thread::spawn(move || solve_shared(&[i; 128], mem, 0))
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), solve_with_memory(&[i as u8; 128], &shared, 0));
+        }
+        assert_eq!(Arc::strong_count(&shared), 1);
+
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        std::thread::scope(|scope| {
+            let solution = scope.spawn(|| solve_with_memory(&data, mem, 4)).join().unwrap();
+            assert!(verify(pubkey, &data, &solution.unwrap(), 4));
+        });
+    }
+
+    #[test]
+    fn test_coverage_gaps() {
+        let (_, mem) = shared_memory();
+        assert_eq!(mem.coverage_gaps(), vec![]);
+
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        present[31] &= !0x80;
+        let mem = single_seed_memory(present);
+        let gaps = mem.coverage_gaps();
+        assert_eq!(gaps.len(), 512);
+        assert_eq!(gaps[..3], [(0, 0), (0, 255), (1, 0)]);
+        assert_eq!(mem.coverage_memory().coverage_gaps(), gaps);
+    }
+
+    #[test]
+    fn test_pack_file_escalating() {
+        let (pubkey, mem) = shared_memory();
+        let data: Vec<u8> = (0..600u32).map(|i| (i * 71 % 251) as u8).collect();
+        let ramp = |i: usize| 2 * i as u32;
+        let solutions = pack_file_escalating_with_memory(&data, mem, ramp).unwrap();
+        assert_eq!(solutions.len(), 5);
+        for (i, (solution, chunk)) in solutions.iter().zip(data.chunks(128)).enumerate() {
+            assert!(verify(pubkey, &pad_chunk(chunk), solution, ramp(i)));
+        }
+        assert_eq!(unpack_slice(pubkey, &solutions, data.len()), data);
+
+        // Target 0 is unreachable, so the first chunk holding a zero fails.
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        let mem = single_seed_memory(present);
+        let mut data = [1u8; 400];
+        data[300] = 0;
+        assert_eq!(pack_file_escalating_with_memory(&data, &mem, |_| 0), Err(PackxError::ChunkUnsolved { index: 2 }));
+    }
+
+    #[test]
+    fn test_solve_with_fixed_nonces() {
+        let pubkey = [3u8; 32];
+        let layout = Solution::new(
+            9,
+            core::array::from_fn(|g| (g * 37) as u8),
+            core::array::from_fn(|i| (i as u8).wrapping_mul(13)),
+        );
+        let data = layout.unpack(&pubkey);
+
+        // Another seed matching a whole group has odds around 2^-56, so the layout comes back.
+        assert_eq!(solve_with_fixed_nonces(&data, &pubkey, 9, layout.nonces, 0), Some(layout));
+        assert_eq!(solve_with_fixed_nonces(&data, &pubkey, 9, layout.nonces, layout.difficulty() + 1), None);
+
+        let mut other = data;
+        other[77] ^= 1;
+        assert_eq!(solve_with_fixed_nonces(&other, &pubkey, 9, layout.nonces, 0), None);
+        assert_eq!(solve_with_fixed_nonces(&data, &pubkey, 10, layout.nonces, 0), None);
+    }
+
+    #[test]
+    fn test_prefault() {
+        touch_pages(&[]);
+        touch_pages(&[1]);
+        touch_pages(&[7u8; PREFAULT_STRIDE * 3 + 1]);
+
+        let (_, mem) = shared_memory();
+        mem.prefault();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        assert!(solve_with_memory(&data, mem, 0).is_some());
+    }
+
+    #[test]
+    fn test_group_candidate_report() {
+        let (_, mem) = shared_memory();
+        let data: [u8; 128] = core::array::from_fn(|i| (i as u8).wrapping_mul(71));
+        let table = &mem.tables[2];
+        for g in [0, 9] {
+            let report = group_candidate_report(&data, g, table);
+            assert_eq!(report.targets, data[g * 8..g * 8 + 8]);
+            assert_eq!(report.covering as usize, group_candidates(&data, g, table).len());
+            for (k, &t) in report.targets.iter().enumerate() {
+                assert_eq!(report.reach[k] as usize, table.present.iter().filter(|row| bit_test(row, t)).count());
             }
+            assert!(report.reach.iter().all(|&r| r >= report.covering));
         }
-        None
+
+        // Only seed 0 has targets, and target 0 isn't one of them.
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        let mem = single_seed_memory(present);
+        let mut data = [5u8; 128];
+        data[8 + 3] = 0;
+        let report = group_candidate_report(&data, 1, &mem.tables[0]);
+        assert_eq!(report.reach, [1, 1, 1, 0, 1, 1, 1, 1]);
+        assert_eq!((report.covering, report.bottleneck()), (0, 3));
     }
 
     #[test]
-    fn test_solve_and_verify() {
-        let mut rng = rand::thread_rng();
-        let mut pubkey = [0u8; 32];
-        let mut data = [0u8; 128];
-        rng.fill_bytes(&mut pubkey);
-        rng.fill_bytes(&mut data);
+    fn test_covering_bumps() {
+        let (_, mem) = shared_memory();
+        let data = [0x3Du8; 128];
+        let bumps = covering_bumps(&data, mem);
+        for bump in ALL_BUMPS {
+            assert_eq!(bumps.contains(&bump), combination_count(&data, &mem.tables[bump as usize]) > 0);
+        }
+        assert_eq!(solve_with_memory(&data, mem, 0).map(|s| s.bump), bumps.first().copied());
 
-        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
-        assert!(verify(&pubkey, &data, &solution, TEST_DIFFICULTY));
+        let mut present = [0xFFu8; 32];
+        present[0] &= !1;
+        assert!(covering_bumps(&[0u8; 128], &single_seed_memory(present)).is_empty());
     }
 
     #[test]
-    fn test_serialize_deserialize_roundtrip() {
-        let solution = Solution { bump: 3, seeds: [9; 16], nonces: [7; 128] };
-        let ser = serialize(&solution);
-        let de = deserialize(&ser);
-        assert_eq!(solution.bump, de.bump);
-        assert_eq!(solution.seeds, de.seeds);
-        assert_eq!(solution.nonces, de.nonces);
+    fn test_verify_batch() {
+        let (pubkey, mem) = shared_memory();
+        let chunks: Vec<[u8; 128]> = (0..3u8).map(|i| [i.wrapping_mul(31); 128]).collect();
+        let solutions: Vec<Solution> = chunks
+            .iter()
+            .map(|data| solve_with_memory(data, mem, TEST_DIFFICULTY).expect("solve failed"))
+            .collect();
+
+        let mut items: Vec<(&[u8; 128], &Solution)> = chunks.iter().zip(&solutions).collect();
+        assert_eq!(verify_batch(pubkey, &items, TEST_DIFFICULTY), vec![true; 3]);
+
+        items[0].1 = &solutions[1];
+        items[1].1 = &solutions[0];
+        assert_eq!(verify_batch(pubkey, &items, TEST_DIFFICULTY), vec![false, false, true]);
+        assert!(verify_batch(pubkey, &[], TEST_DIFFICULTY).is_empty());
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_verify_failure_wrong_data() {
-        let mut rng = rand::thread_rng();
-        let mut pubkey = [0u8; 32];
-        let mut data = [0u8; 128];
-        rng.fill_bytes(&mut pubkey);
-        rng.fill_bytes(&mut data);
+    fn test_verify_stream() {
+        let (pubkey, mem) = shared_memory();
+        let chunks: Vec<[u8; 128]> = (0..3u8).map(|i| [i.wrapping_mul(57); 128]).collect();
+        let data: Vec<u8> = chunks.concat();
+        let mut solutions: Vec<u8> = chunks
+            .iter()
+            .flat_map(|chunk| solve_with_memory(chunk, mem, TEST_DIFFICULTY).expect("solve failed").to_bytes())
+            .collect();
 
-        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
-        let mut wrong = data;
-        wrong[0] = wrong[0].wrapping_add(1);
-        assert!(!verify(&pubkey, &wrong, &solution, TEST_DIFFICULTY));
+        let check = |data: &[u8], solutions: &[u8]| {
+            let result = verify_stream(pubkey, data, solutions, TEST_DIFFICULTY);
+            #[cfg(feature = "rayon")]
+            assert_eq!(
+                format!("{:?}", verify_stream_parallel(pubkey, data, solutions, TEST_DIFFICULTY)),
+                format!("{result:?}")
+            );
+            result
+        };
+        assert_eq!(check(&data, &solutions).unwrap(), 3);
+        assert_eq!(check(&[], &[]).unwrap(), 0);
+        assert!(matches!(check(&data[..128 * 2], &solutions), Err(VerifyError::Truncated { index: 2 })));
+        assert!(matches!(check(&data, &solutions[..SOLUTION_SIZE * 3 - 1]), Err(VerifyError::Truncated { index: 2 })));
+
+        solutions[SOLUTION_SIZE + 20] ^= 1;
+        assert!(matches!(check(&data, &solutions), Err(VerifyError::Invalid { index: 1 })));
     }
 
+    #[cfg(feature = "ffi")]
     #[test]
-    fn test_verify_failure_wrong_pubkey() {
+    fn test_ffi_roundtrip() {
+        use crate::ffi::*;
+        use core::ptr;
+
+        let pubkey = [4u8; 32];
+        let data = [0x5Au8; 128];
+        let mut out = [0u8; SOLUTION_SIZE];
+        unsafe {
+            assert!(packx_build_memory(ptr::null()).is_null());
+            let mem = packx_build_memory(&pubkey);
+            assert!(!mem.is_null());
+
+            assert_eq!(packx_solve(ptr::null(), &data, 0, &mut out), PACKX_NULL_ARG);
+            assert_eq!(packx_solve(mem, &data, TEST_DIFFICULTY, &mut out), PACKX_OK);
+
+            assert_eq!(packx_verify(&pubkey, &data, &out, TEST_DIFFICULTY), PACKX_OK);
+            assert_eq!(packx_verify(&pubkey, &[0u8; 128], &out, TEST_DIFFICULTY), PACKX_INVALID);
+            assert_eq!(packx_verify(&pubkey, &data, ptr::null(), 0), PACKX_NULL_ARG);
+
+            packx_free_memory(mem);
+            packx_free_memory(ptr::null_mut());
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_solve_wasm() {
+        let pubkey = [3u8; 32];
+        let data = [9u8; 128];
+        assert_eq!(solve_wasm(&pubkey[..31], &data, 0), None);
+        assert_eq!(solve_wasm(&pubkey, &data[..127], 0), None);
+
+        let bytes = solve_wasm(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
+        assert!(verify_bytes(&pubkey, &data, bytes.as_slice().try_into().unwrap(), TEST_DIFFICULTY));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_matches_to_bytes() {
+        let solution = Solution { bump: 3, seeds: [9; 16], nonces: core::array::from_fn(|i| i as u8) };
+        let encoded = borsh::to_vec(&solution).expect("borsh serialize failed");
+        assert_eq!(encoded, solution.to_bytes());
+        let decoded: Solution = borsh::from_slice(&encoded).expect("borsh deserialize failed");
+        assert_eq!(decoded, solution);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_and_bincode_roundtrip() {
+        let solution = Solution { bump: 3, seeds: [9; 16], nonces: core::array::from_fn(|i| i as u8) };
+
+        let json = serde_json::to_string(&solution).expect("json serialize failed");
+        let from_json: Solution = serde_json::from_str(&json).expect("json deserialize failed");
+        assert_eq!(from_json.bump, solution.bump);
+        assert_eq!(from_json.seeds, solution.seeds);
+        assert_eq!(from_json.nonces, solution.nonces);
+
+        let encoded = bincode::serialize(&solution).expect("bincode serialize failed");
+        let from_bincode: Solution = bincode::deserialize(&encoded).expect("bincode deserialize failed");
+        assert_eq!(from_bincode, solution);
+        assert_eq!(bincode::serialize(&from_bincode).expect("bincode serialize failed"), encoded);
+    }
+
+    #[test]
+    fn test_pack_file_parallel_roundtrip() {
         let mut rng = rand::thread_rng();
         let mut pubkey = [0u8; 32];
-        let mut data = [0u8; 128];
         rng.fill_bytes(&mut pubkey);
+        let mut data = vec![0u8; 1000];
         rng.fill_bytes(&mut data);
 
-        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
-        let mut other = pubkey;
-        other[0] ^= 1;
-        assert!(!verify(&other, &data, &solution, TEST_DIFFICULTY));
+        let solutions = pack_file_parallel(&pubkey, &data, 0).expect("pack failed");
+        assert_eq!(solutions.len(), 8);
+        assert_eq!(unpack_slice(&pubkey, &solutions, data.len()), data);
     }
 
+    #[cfg(feature = "rayon")]
     #[test]
-    fn test_unpack_roundtrip() {
+    fn test_solve_with_memory_parallel() {
+        let (pubkey, mem) = shared_memory();
         let mut rng = rand::thread_rng();
-        let mut pubkey = [0u8; 32];
         let mut data = [0u8; 128];
-        rng.fill_bytes(&mut pubkey);
         rng.fill_bytes(&mut data);
 
-        let solution = solve_lightweight(&pubkey, &data, TEST_DIFFICULTY).expect("solve failed");
-        assert_eq!(unpack(&pubkey, &solution), data);
+        let solution = solve_with_memory_parallel(&data, mem, TEST_DIFFICULTY, 4).expect("solve failed");
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+
+        // 0 runs on whichever pool the caller installed.
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let solution = pool.install(|| solve_with_memory_parallel(&data, mem, TEST_DIFFICULTY, 0)).expect("solve failed");
+        assert!(verify(pubkey, &data, &solution, TEST_DIFFICULTY));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_build_one_bump_parallel_matches_sequential() {
+        let (pubkey, mem) = shared_memory();
+        for bump in [0u8, 200] {
+            let table = build_one_bump_parallel(pubkey, bump);
+            assert_eq!(table.nonces, mem.tables[bump as usize].nonces);
+            assert_eq!(table.present, mem.tables[bump as usize].present);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_solve_one_bump_parallel_matches_sequential() {
+        // Two seeds reach every target, so there are exactly 2^16 combinations.
+        let mut table = alloc_table();
+        for seed in 0..2 {
+            table.present[seed] = [0xFF; 32];
+            table.nonces[seed] = core::array::from_fn(|t| (t * 7 + seed) as u8);
+        }
+        let data = [0x5Eu8; 128];
+        assert_eq!(combination_count(&data, &table), 1 << 16);
+
+        for difficulty in [8, 12, 20] {
+            let sequential = solve_one_bump(&data, 3, &table, difficulty);
+            assert_eq!(solve_one_bump_parallel(&data, 3, &table, difficulty, 1), sequential);
+
+            for num_threads in [0, 4] {
+                let parallel = solve_one_bump_parallel(&data, 3, &table, difficulty, num_threads);
+                assert_eq!(parallel.is_some(), sequential.is_some());
+                if let Some(solution) = parallel {
+                    assert!(solution.difficulty() >= difficulty);
+                    assert_eq!(solution.bump, 3);
+                }
+            }
+        }
+
+        // Dense real tables exceed the split threshold and take the sequential scan.
+        let (_, mem) = shared_memory();
+        let data = [0x11u8; 128];
+        assert!(combination_count(&data, &mem.tables[0]) > PARALLEL_SPLIT_MAX);
+        assert_eq!(
+            solve_one_bump_parallel(&data, 0, &mem.tables[0], TEST_DIFFICULTY, 4),
+            solve_one_bump(&data, 0, &mem.tables[0], TEST_DIFFICULTY)
+        );
+    }
+
+    /// Property tests of the solve -> verify invariant over random pubkeys and data.
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Any solution, valid or not; shrinks each field toward zero.
+        fn arb_solution() -> impl Strategy<Value = Solution> {
+            (any::<u8>(), any::<[u8; 16]>(), proptest::collection::vec(any::<u8>(), 128))
+                .prop_map(|(bump, seeds, nonces)| Solution::new(bump, seeds, nonces.try_into().unwrap()))
+        }
+
+        /// Solve against single-bump tables, which is enough to exercise build, unpack, and
+        /// verify together without building a whole memory per case.
+        fn solve_first_bumps(pubkey: &[u8; 32], data: &[u8; 128], difficulty: u32) -> Option<Solution> {
+            (0u8..=TEST_BUMP_TRIES).find_map(|bump| solve_one_bump(data, bump, &build_one_bump(pubkey, bump), difficulty))
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(16))]
+
+            #[test]
+            fn solve_output_verifies(
+                pubkey in any::<[u8; 32]>(),
+                data in proptest::collection::vec(any::<u8>(), 128),
+                difficulty in 0u32..4,
+                tamper in 0usize..SOLUTION_SIZE - 1,
+                flip in 1u8..=255,
+            ) {
+                let data: [u8; 128] = data.try_into().unwrap();
+                let solution = solve_first_bumps(&pubkey, &data, difficulty);
+                prop_assume!(solution.is_some());
+                let solution = solution.unwrap();
+                prop_assert!(verify(&pubkey, &data, &solution, difficulty));
+                prop_assert!(solution.difficulty() >= difficulty);
+
+                // Flip one seed or nonce byte (never the bump, which picks the table).
+                let mut bytes = serialize(&solution);
+                bytes[1 + tamper] ^= flip;
+                let tampered = deserialize(&bytes);
+                let still_valid = unpack(&pubkey, &tampered) == data && tampered.difficulty() >= difficulty;
+                prop_assert_eq!(verify(&pubkey, &data, &tampered, difficulty), still_valid);
+            }
+
+            #[test]
+            fn any_solution_verifies_against_its_own_unpacking(pubkey in any::<[u8; 32]>(), solution in arb_solution()) {
+                prop_assert_eq!(deserialize(&serialize(&solution)), solution);
+                let data = unpack(&pubkey, &solution);
+                prop_assert!(verify(&pubkey, &data, &solution, 0));
+                prop_assert_eq!(unpack_checked(&pubkey, &data, &solution), Ok(data));
+                prop_assert_eq!(verify(&pubkey, &data, &solution, solution.difficulty() + 1), false);
+            }
+        }
+    }
+}
+
+/// Tests that only touch the allocation-free verify/unpack path, so they also run
+/// under `cargo test --no-default-features`.
+#[cfg(test)]
+mod core_tests {
+    use super::*;
+
+    const VECTOR_PUBKEY: [u8; 32] = [0x11; 32];
+    const VECTOR_DIFFICULTY: u32 = 4;
+
+    /// Solution for VECTOR_PUBKEY / vector_data() at difficulty 4.
+    const VECTOR_SOLUTION: [u8; SOLUTION_SIZE] = [
+        0x00, // bump
+        // seeds
+        0x34, 0x50, 0x41, 0x8a, 0x14, 0x1d, 0x6d, 0x89, 0x17, 0x24, 0x45, 0x0e, 0x27, 0x7c, 0x08, 0x11,
+        // nonces
+        0xee, 0x06, 0x72, 0x44, 0x15, 0xae, 0x2e, 0xbd, 0xd3, 0x5e, 0x58, 0xac, 0x38, 0x33, 0x55, 0xc8,
+        0x5d, 0x42, 0x16, 0x2e, 0x11, 0x46, 0xaf, 0x8a, 0x7d, 0xf9, 0xe1, 0x4d, 0x62, 0x51, 0xb8, 0xf7,
+        0x04, 0x76, 0x95, 0xfc, 0xdc, 0xa2, 0x0c, 0x57, 0xfe, 0x85, 0xd4, 0xce, 0x63, 0x17, 0xe7, 0x23,
+        0x0f, 0x48, 0x4a, 0x89, 0x18, 0x4e, 0x5a, 0x8b, 0x82, 0x21, 0x69, 0xa4, 0x4f, 0x42, 0x76, 0x1c,
+        0xed, 0x5d, 0x61, 0x9d, 0x05, 0x0c, 0x38, 0x7c, 0x1c, 0x65, 0x79, 0xb4, 0x16, 0x81, 0xd3, 0xf8,
+        0x67, 0x9b, 0x15, 0x84, 0x2b, 0x55, 0x81, 0xe7, 0x41, 0xde, 0xe4, 0xbd, 0x79, 0x13, 0x3e, 0xa8,
+        0x4f, 0x39, 0xef, 0xfe, 0xa1, 0x9e, 0xe4, 0xba, 0x10, 0x84, 0x26, 0x3f, 0x32, 0xf8, 0x8e, 0xd0,
+        0xb7, 0x32, 0x07, 0x3b, 0x1a, 0xb9, 0x60, 0x76, 0x9e, 0x01, 0x51, 0x9f, 0x78, 0x0f, 0xdc, 0x2a,
+    ];
+
+    fn vector_data() -> [u8; 128] {
+        core::array::from_fn(|i| (i as u8).wrapping_mul(37).wrapping_add(5))
+    }
+
+    #[test]
+    fn test_verify_precomputed_solution() {
+        let solution = deserialize(&VECTOR_SOLUTION);
+        let data = vector_data();
+        assert_eq!(unpack(&VECTOR_PUBKEY, &solution), data);
+        assert_eq!(solution.difficulty(), VECTOR_DIFFICULTY);
+        assert!(verify(&VECTOR_PUBKEY, &data, &solution, VECTOR_DIFFICULTY));
+        assert!(!verify(&VECTOR_PUBKEY, &data, &solution, VECTOR_DIFFICULTY + 1));
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).expect("exact length should parse");
+        assert_eq!(<[u8; SOLUTION_SIZE]>::from(solution), VECTOR_SOLUTION);
+
+        assert_eq!(
+            Solution::try_from(&VECTOR_SOLUTION[..SOLUTION_SIZE - 1]),
+            Err(PackxError::BadLength { expected: SOLUTION_SIZE, got: SOLUTION_SIZE - 1 })
+        );
+        assert_eq!(
+            Solution::try_from(&[0u8; SOLUTION_SIZE + 1][..]),
+            Err(PackxError::BadLength { expected: SOLUTION_SIZE, got: SOLUTION_SIZE + 1 })
+        );
+    }
+
+    #[test]
+    fn test_deserialize_checked() {
+        let mut buf = [0u8; SOLUTION_SIZE + 3];
+        buf[3..].copy_from_slice(&VECTOR_SOLUTION);
+        assert_eq!(deserialize_checked(&buf[3..]), Ok(deserialize(&VECTOR_SOLUTION)));
+
+        assert_eq!(deserialize_checked(&[]), Err(PackxError::BadLength { expected: SOLUTION_SIZE, got: 0 }));
+        assert_eq!(
+            deserialize_checked(&buf[1..]),
+            Err(PackxError::BadLength { expected: SOLUTION_SIZE, got: SOLUTION_SIZE + 2 })
+        );
+        assert_eq!(core::mem::align_of::<Solution>(), 1);
+    }
+
+    #[test]
+    fn test_difficulty_from_bytes_and_solution_hash() {
+        let solution = deserialize(&VECTOR_SOLUTION);
+        assert_eq!(difficulty_from_bytes(&VECTOR_SOLUTION), VECTOR_DIFFICULTY);
+        assert_eq!(difficulty_from_bytes(&VECTOR_SOLUTION), solution.difficulty());
+        assert_eq!(get_difficulty(solution.solution_hash()), VECTOR_DIFFICULTY);
+        assert_eq!(solution.solution_hash(), compute_hash(&[&VECTOR_SOLUTION]));
+    }
+
+    #[test]
+    fn test_verify_bytes_agrees_with_verify() {
+        let solution = deserialize(&VECTOR_SOLUTION);
+        let data = vector_data();
+        let mut wrong = data;
+        wrong[127] ^= 1;
+        for difficulty in 0..=VECTOR_DIFFICULTY + 1 {
+            for d in [&data, &wrong] {
+                assert_eq!(
+                    verify_bytes(&VECTOR_PUBKEY, d, &VECTOR_SOLUTION, difficulty),
+                    verify(&VECTOR_PUBKEY, d, &solution, difficulty)
+                );
+            }
+        }
+        assert!(verify_bytes(&VECTOR_PUBKEY, &data, &VECTOR_SOLUTION, VECTOR_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_meets_target_big_endian() {
+        let mut target = [0u8; 32];
+        target[1] = 0x80;
+        let mut hash = target;
+        assert!(meets_target(&hash, &target));
+        hash[31] = 1;
+        assert!(!meets_target(&hash, &target));
+        hash = [0u8; 32];
+        hash[1] = 0x7F;
+        hash[31] = 0xFF;
+        assert!(meets_target(&hash, &target));
+        assert!(meets_target(&[0xFF; 32], &[0xFF; 32]));
+    }
+
+    #[test]
+    fn test_leading_zero_bits() {
+        assert_eq!(leading_zero_bits(&[]), 0);
+        assert_eq!(leading_zero_bits(&[0]), 8);
+        assert_eq!(leading_zero_bits(&[0u8; 64]), 512);
+        assert_eq!(leading_zero_bits(&[0x00, 0x1F, 0x00]), 11);
+        assert_eq!(leading_zero_bits(&[0x80, 0x00]), 0);
+
+        let hash = compute_hash(&[b"leading zeros"]);
+        assert_eq!(leading_zero_bits(&hash), get_difficulty(hash));
+    }
+
+    #[test]
+    fn test_get_difficulty_boundaries() {
+        assert_eq!(get_difficulty([0u8; 32]), MAX_DIFFICULTY);
+
+        let mut hash = [0xFFu8; 32];
+        assert_eq!(get_difficulty(hash), 0);
+        hash[0] = 0b0001_0000;
+        assert_eq!(get_difficulty(hash), 3);
+
+        // Zeros only count through the first set bit, not past it.
+        let mut hash = [0u8; 32];
+        hash[1] = 0b0000_0001;
+        assert_eq!(get_difficulty(hash), 15);
+        hash = [0u8; 32];
+        hash[31] = 1;
+        assert_eq!(get_difficulty(hash), MAX_DIFFICULTY - 1);
+    }
+
+    #[test]
+    fn test_unpack_checked_reports_first_mismatch() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let data = vector_data();
+        assert_eq!(unpack_checked(&VECTOR_PUBKEY, &data, &solution), Ok(data));
+
+        let mut corrupted = solution;
+        corrupted.nonces[42] ^= 1;
+        let err = unpack_checked(&VECTOR_PUBKEY, &data, &corrupted).unwrap_err();
+        assert_eq!((err.group, err.index, err.expected), (5, 42, data[42]));
+        assert_eq!(err.actual, unpack(&VECTOR_PUBKEY, &corrupted)[42]);
+        assert_ne!(err.actual, err.expected);
+
+        let mut wrong_data = data;
+        wrong_data[127] ^= 0xFF;
+        let err = unpack_checked(&VECTOR_PUBKEY, &wrong_data, &solution).unwrap_err();
+        assert_eq!((err.group, err.index, err.actual), (15, 127, data[127]));
+    }
+
+    #[test]
+    fn test_verify_reconstruction_ignores_difficulty() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let data = vector_data();
+        assert!(solution.difficulty() < 64);
+        assert!(!verify(&VECTOR_PUBKEY, &data, &solution, 64));
+        assert!(verify_reconstruction(&VECTOR_PUBKEY, &data, &solution));
+
+        let mut wrong = data;
+        wrong[0] ^= 1;
+        assert!(!verify_reconstruction(&VECTOR_PUBKEY, &wrong, &solution));
+    }
+
+    #[test]
+    fn test_verify_with_hash() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let data = vector_data();
+        let hash = solution.solution_hash();
+        let difficulty = solution.difficulty();
+        assert!(verify_with_hash(&VECTOR_PUBKEY, &data, &solution, &hash, difficulty));
+        assert!(!verify_with_hash(&VECTOR_PUBKEY, &data, &solution, &hash, difficulty + 1));
+
+        let mut tampered = hash;
+        tampered[0] ^= 0x80;
+        assert!(!verify_with_hash(&VECTOR_PUBKEY, &data, &solution, &tampered, 0));
+        assert!(!verify_with_hash(&VECTOR_PUBKEY, &data, &solution, &[0; 32], 0));
+
+        let mut wrong = data;
+        wrong[0] ^= 1;
+        assert!(!verify_with_hash(&VECTOR_PUBKEY, &wrong, &solution, &hash, 0));
+    }
+
+    #[test]
+    fn test_solution_diff() {
+        let a = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        assert!(a.diff(&a).is_empty());
+        assert_eq!(a.diff(&a).groups().count(), 0);
+
+        let mut b = a;
+        b.seeds[3] ^= 1;
+        b.nonces[42] ^= 1;
+        b.nonces[127] ^= 1;
+        let diff = a.diff(&b);
+        assert_eq!(diff, SolutionDiff { bump: false, seeds: 1 << 3, nonces: (1 << 42) | (1 << 127) });
+        assert_eq!(diff.groups().collect::<Vec<_>>(), vec![3, 5, 15]);
+        assert_eq!(b.diff(&a), diff);
+
+        b = a;
+        b.bump ^= 1;
+        assert_eq!(a.diff(&b), SolutionDiff { bump: true, ..Default::default() });
+        assert!(!a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn test_new_checked() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let data = vector_data();
+        let checked = Solution::new_checked(&VECTOR_PUBKEY, &data, solution.bump, solution.seeds, solution.nonces);
+        assert_eq!(checked, Ok(solution));
+
+        let mut nonces = solution.nonces;
+        nonces[42] ^= 1;
+        let err = Solution::new_checked(&VECTOR_PUBKEY, &data, solution.bump, solution.seeds, nonces).unwrap_err();
+        assert_eq!(err, PackxError::WrongByte { index: 42 });
+        let err = Solution::new_checked(&VECTOR_PUBKEY, &data, solution.bump ^ 1, solution.seeds, solution.nonces);
+        assert!(matches!(err, Err(PackxError::WrongByte { .. })));
+    }
+
+    #[test]
+    fn test_solution_v2_roundtrip() {
+        let mut solution = SolutionV2 { bump: 3, seeds: [0u8; 16], nonces: [0u16; 128] };
+        for (i, nonce) in solution.nonces.iter_mut().enumerate() {
+            *nonce = (i as u16).wrapping_mul(517);
+        }
+        solution.seeds[15] = 0xEE;
+
+        let bytes = serialize_v2(&solution);
+        assert_eq!(bytes.len(), SOLUTION_V2_SIZE);
+        assert_eq!((bytes[0], bytes[16]), (3, 0xEE));
+        assert_eq!(&bytes[17 + 2..17 + 4], &517u16.to_le_bytes());
+        assert_eq!(deserialize_v2(&bytes), solution);
+    }
+
+    #[test]
+    fn test_generic_solution_layout() {
+        assert_eq!(Solution128::SIZE, SOLUTION_SIZE);
+        assert_eq!(Solution::<8, 64>::SIZE, 73);
+        assert_eq!(Solution::<32, 256>::SIZE, 289);
+
+        let solution = Solution::<8, 64>::new(5, [1; 8], [2; 64]);
+        let bytes = solution.as_bytes();
+        assert_eq!(bytes.len(), 73);
+        assert_eq!((bytes[0], bytes[8], bytes[9], bytes[72]), (5, 1, 2, 2));
+
+        let standard = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        assert_eq!(standard.as_bytes(), &VECTOR_SOLUTION[..]);
+        assert_eq!(standard.difficulty(), difficulty_from_bytes(&VECTOR_SOLUTION));
+    }
+
+    #[test]
+    fn test_solution_default_is_zeroed() {
+        assert_eq!(Solution::default().to_bytes(), [0u8; SOLUTION_SIZE]);
+        assert_eq!(Solution::default(), Solution::zeroed());
+
+        let solution = Solution { bump: 7, ..Default::default() };
+        assert_eq!(solution.to_bytes()[0], 7);
+        assert!(solution.to_bytes()[1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_solution_hash_set_dedup() {
+        use std::collections::HashSet;
+
+        let a = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let mut b = a;
+        b.nonces[127] ^= 1;
+
+        let set: HashSet<Solution> = [a, b, a, Solution::default(), a].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&b));
+
+        assert_eq!(a.id(), a.solution_hash());
+        assert_ne!(a.id(), b.id());
+    }
+
+    #[test]
+    fn test_expected_iterations_and_solve_time() {
+        use core::time::Duration;
+
+        assert_eq!(expected_iterations(0), 1.0);
+        assert_eq!(expected_iterations(12), 4096.0);
+        assert_eq!(expected_iterations(64), 18446744073709551616.0);
+        assert_eq!(expected_iterations(MAX_DIFFICULTY), expected_iterations(u32::MAX));
+        assert!(expected_iterations(u32::MAX).is_finite());
+
+        assert_eq!(estimated_solve_time(10, 1024.0), Duration::from_secs(1));
+        assert_eq!(estimated_solve_time(0, 4.0), Duration::from_millis(250));
+        assert_eq!(estimated_solve_time(100, 1e6), Duration::MAX);
+        assert_eq!(estimated_solve_time(8, 0.0), Duration::MAX);
+        assert_eq!(estimated_solve_time(8, f64::NAN), Duration::MAX);
+    }
+
+    #[test]
+    fn test_verify_rejects_last_byte_mismatch() {
+        let solution = Solution::try_from(&VECTOR_SOLUTION[..]).unwrap();
+        let data = vector_data();
+        assert!(verify(&VECTOR_PUBKEY, &data, &solution, 0));
+
+        let mut last_wrong = data;
+        last_wrong[127] ^= 1;
+        assert!(!verify(&VECTOR_PUBKEY, &last_wrong, &solution, 0));
+        assert!(!verify_bytes(&VECTOR_PUBKEY, &last_wrong, &VECTOR_SOLUTION, 0));
+        assert!(!verify_with_mode(&VECTOR_PUBKEY, &last_wrong, &solution, 0, DifficultyMode::SolutionOnly));
+
+        let mut first_wrong = data;
+        first_wrong[0] ^= 1;
+        assert!(!verify(&VECTOR_PUBKEY, &first_wrong, &solution, 0));
+    }
+
+    #[test]
+    fn test_solve_one_bump_nostd_without_alloc() {
+        // Only seed 3 reaches anything, so it must be chosen for every group.
+        let mut nonces = [[0u8; 256]; 256];
+        let mut present = [[0u8; 32]; 256];
+        present[3] = [0xFF; 32];
+        for (target, nonce) in nonces[3].iter_mut().enumerate() {
+            *nonce = !(target as u8);
+        }
+        let table = SeedTableRef { nonces: &nonces, present: &present };
+        let mut scratch = [SeedCandidate { seed: 0, nonces8: [0; 8] }; NOSTD_SCRATCH_LEN];
+
+        let data: [u8; 128] = core::array::from_fn(|i| i as u8);
+        let solution = solve_one_bump_nostd(&data, 9, table, 0, &mut scratch).unwrap();
+        assert_eq!(solution.bump, 9);
+        assert_eq!(solution.seeds, [3; 16]);
+        assert!(solution.nonces.iter().zip(&data).all(|(&nonce, &byte)| nonce == !byte));
+
+        present[3][0] = 0xFE; // target 0 unreachable
+        let table = SeedTableRef { nonces: &nonces, present: &present };
+        assert_eq!(solve_one_bump_nostd(&data, 9, table, 0, &mut scratch), None);
     }
 }
@@ -0,0 +1,156 @@
+//! Saving and loading `SolverMemory` tables to disk.
+//!
+//! File layout: an 8-byte magic, a 1-byte hash algorithm id, the 32-byte pubkey the tables
+//! were built for, then the `SolverMemory::to_bytes` layout: for each bump 0..=255 its nonces
+//! (256 * 256 bytes) followed by its present bitsets (256 * 32 bytes).
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::{alloc_table, HashAlgo, SolverMemory, SEED_TABLE_BYTES};
+#[cfg(feature = "mmap")]
+use crate::{touch_pages, BumpSearch, SeedTableRef, SolveLimits, Solution};
+
+const MAGIC: &[u8; 8] = b"PACKXMEM";
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 32;
+#[cfg(feature = "mmap")]
+const NONCES_SIZE: usize = 256 * 256;
+const TABLE_SIZE: usize = SEED_TABLE_BYTES;
+const FILE_SIZE: usize = HEADER_SIZE + 256 * TABLE_SIZE;
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn algo_id(algo: HashAlgo) -> u8 {
+    match algo {
+        HashAlgo::Blake3 => 0,
+        #[cfg(feature = "sha256")]
+        HashAlgo::Sha256 => 1,
+    }
+}
+
+fn algo_from_id(id: u8) -> io::Result<HashAlgo> {
+    match id {
+        0 => Ok(HashAlgo::Blake3),
+        #[cfg(feature = "sha256")]
+        1 => Ok(HashAlgo::Sha256),
+        _ => Err(invalid("unsupported hash algorithm")),
+    }
+}
+
+/// Validate the header against `pubkey` and return the stored hash algorithm.
+fn check_header(header: &[u8], pubkey: &[u8; 32]) -> io::Result<HashAlgo> {
+    if &header[..MAGIC.len()] != MAGIC {
+        return Err(invalid("not a packx solver memory file"));
+    }
+    if &header[MAGIC.len() + 1..HEADER_SIZE] != pubkey {
+        return Err(invalid("solver memory was built for a different pubkey"));
+    }
+    algo_from_id(header[MAGIC.len()])
+}
+
+impl SolverMemory {
+    /// Write the tables to `path`, preceded by a header recording `pubkey`.
+    ///
+    /// Fails without creating the file if the memory records a different pubkey.
+    pub fn save_to_path(&self, pubkey: &[u8; 32], path: impl AsRef<Path>) -> io::Result<()> {
+        if self.pubkey().is_some_and(|built_for| built_for != pubkey) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "solver memory was built for a different pubkey"));
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(MAGIC)?;
+        out.write_all(&[algo_id(self.algo)])?;
+        out.write_all(pubkey)?;
+        for table in self.tables.iter() {
+            out.write_all(bytemuck::cast_slice(&table.nonces))?;
+            out.write_all(bytemuck::cast_slice(&table.present))?;
+        }
+        out.flush()
+    }
+
+    /// Load tables written by `save_to_path`, failing if they were built for another pubkey.
+    pub fn load_from_path(path: impl AsRef<Path>, pubkey: &[u8; 32]) -> io::Result<SolverMemory> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() != FILE_SIZE as u64 {
+            return Err(invalid("solver memory file has the wrong size"));
+        }
+
+        let mut input = BufReader::new(file);
+        let mut header = [0u8; HEADER_SIZE];
+        input.read_exact(&mut header)?;
+        let algo = check_header(&header, pubkey)?;
+
+        let mut tables = Vec::with_capacity(256);
+        for _ in 0..256 {
+            let mut table = alloc_table();
+            input.read_exact(bytemuck::cast_slice_mut(&mut table.nonces))?;
+            input.read_exact(bytemuck::cast_slice_mut(&mut table.present))?;
+            tables.push(table);
+        }
+
+        Ok(SolverMemory::new(tables.into_boxed_slice(), algo).with_pubkey(pubkey))
+    }
+
+    /// Map a file written by `save_to_path` read-only, so several processes can share one
+    /// copy of the tables instead of each allocating 18 MiB. Pages are faulted in lazily.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the mapping is alive.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn mmap_from_path(
+        path: impl AsRef<Path>,
+        pubkey: &[u8; 32],
+    ) -> io::Result<MappedSolverMemory> {
+        let file = File::open(path)?;
+        let map = memmap2::Mmap::map(&file)?;
+        if map.len() != FILE_SIZE {
+            return Err(invalid("solver memory file has the wrong size"));
+        }
+        let algo = check_header(&map[..HEADER_SIZE], pubkey)?;
+        Ok(MappedSolverMemory { map, algo })
+    }
+}
+
+/// All bumps for one pubkey, backed by a read-only file mapping.
+#[cfg(feature = "mmap")]
+pub struct MappedSolverMemory {
+    map: memmap2::Mmap,
+    algo: HashAlgo,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedSolverMemory {
+    /// View of the table for `bump`.
+    pub fn table(&self, bump: u8) -> SeedTableRef<'_> {
+        let start = HEADER_SIZE + bump as usize * TABLE_SIZE;
+        let nonces = &self.map[start..start + NONCES_SIZE];
+        let present = &self.map[start + NONCES_SIZE..start + TABLE_SIZE];
+        SeedTableRef {
+            nonces: bytemuck::cast_slice(nonces),
+            present: bytemuck::cast_slice(present),
+        }
+    }
+
+    /// Read one byte per `PREFAULT_STRIDE` of the mapping, faulting the whole file in so the
+    /// first solves don't stall on disk reads. Pages can still be evicted later under
+    /// memory pressure.
+    pub fn prefault(&self) {
+        touch_pages(&self.map);
+    }
+
+    /// Same search as `solve_with_memory`, reading tables from the mapping.
+    pub fn solve(&self, data: &[u8; 128], difficulty: u32) -> Option<Solution> {
+        let mut search = BumpSearch::empty();
+        for bump in 0u8..=u8::MAX {
+            if search.fill(data, bump, self.table(bump)).is_ok() {
+                if let Some(solution) = search.find(difficulty, self.algo, &SolveLimits::UNLIMITED, None) {
+                    return Some(solution);
+                }
+            }
+        }
+        None
+    }
+}
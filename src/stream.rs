@@ -0,0 +1,143 @@
+//! Verifying files of concatenated solutions without loading them whole.
+//!
+//! The data stream holds N chunks of 128 bytes and the solution stream the N matching
+//! 145-byte serialized solutions, in the same order.
+
+use std::fmt;
+use std::io::{self, Read};
+
+use crate::{verify_bytes, SOLUTION_SIZE};
+
+/// Why `verify_stream` stopped before the end of its input.
+#[derive(Debug)]
+pub enum VerifyError {
+    /// Reading either stream failed.
+    Io(io::Error),
+    /// Record `index` did not reconstruct its data or missed the difficulty.
+    Invalid { index: usize },
+    /// A stream ended partway through record `index`, or ended before the other stream.
+    Truncated { index: usize },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Io(err) => write!(f, "read failed: {err}"),
+            VerifyError::Invalid { index } => write!(f, "solution {index} does not verify"),
+            VerifyError::Truncated { index } => write!(f, "streams truncated at record {index}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerifyError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for VerifyError {
+    fn from(err: io::Error) -> Self {
+        VerifyError::Io(err)
+    }
+}
+
+/// Records read per batch by `verify_stream_parallel`.
+#[cfg(feature = "rayon")]
+pub const STREAM_BATCH_RECORDS: usize = 4096;
+
+/// Fill `buf` as far as the reader allows, returning how many bytes were read.
+/// Fewer than `buf.len()` means the reader hit end of stream.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+/// Read the next pair of records; false once both streams end cleanly at record `index`.
+fn next_record<D: Read, S: Read>(
+    data_reader: &mut D,
+    solution_reader: &mut S,
+    data: &mut [u8; 128],
+    solution: &mut [u8; SOLUTION_SIZE],
+    index: usize,
+) -> Result<bool, VerifyError> {
+    match (read_full(data_reader, data)?, read_full(solution_reader, solution)?) {
+        (0, 0) => Ok(false),
+        (128, SOLUTION_SIZE) => Ok(true),
+        _ => Err(VerifyError::Truncated { index }),
+    }
+}
+
+/// Verify every (data, solution) record from two streams against `pubkey`, one record in
+/// memory at a time. Returns the number of records verified, or the first failure.
+pub fn verify_stream<D: Read, S: Read>(
+    pubkey: &[u8; 32],
+    mut data_reader: D,
+    mut solution_reader: S,
+    difficulty: u32,
+) -> Result<usize, VerifyError> {
+    let mut data = [0u8; 128];
+    let mut solution = [0u8; SOLUTION_SIZE];
+    let mut index = 0;
+    while next_record(&mut data_reader, &mut solution_reader, &mut data, &mut solution, index)? {
+        if !verify_bytes(pubkey, &data, &solution, difficulty) {
+            return Err(VerifyError::Invalid { index });
+        }
+        index += 1;
+    }
+    Ok(index)
+}
+
+/// `verify_stream` reading `STREAM_BATCH_RECORDS` records at a time and verifying each batch
+/// on rayon. Reports the same first failure as `verify_stream`.
+#[cfg(feature = "rayon")]
+pub fn verify_stream_parallel<D: Read, S: Read>(
+    pubkey: &[u8; 32],
+    mut data_reader: D,
+    mut solution_reader: S,
+    difficulty: u32,
+) -> Result<usize, VerifyError> {
+    use rayon::prelude::*;
+
+    let mut batch: Vec<([u8; 128], [u8; SOLUTION_SIZE])> = Vec::with_capacity(STREAM_BATCH_RECORDS);
+    let mut verified = 0;
+    loop {
+        batch.clear();
+        let mut data = [0u8; 128];
+        let mut solution = [0u8; SOLUTION_SIZE];
+        let mut read_err = None;
+        while batch.len() < STREAM_BATCH_RECORDS {
+            match next_record(&mut data_reader, &mut solution_reader, &mut data, &mut solution, verified + batch.len()) {
+                Ok(true) => batch.push((data, solution)),
+                Ok(false) => break,
+                Err(err) => {
+                    read_err = Some(err);
+                    break;
+                }
+            }
+        }
+
+        // Records before a read error still get checked, so an invalid one is reported first.
+        let bad = batch.par_iter().position_first(|(data, solution)| !verify_bytes(pubkey, data, solution, difficulty));
+        if let Some(offset) = bad {
+            return Err(VerifyError::Invalid { index: verified + offset });
+        }
+        if let Some(err) = read_err {
+            return Err(err);
+        }
+        verified += batch.len();
+        if batch.len() < STREAM_BATCH_RECORDS {
+            return Ok(verified);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Counters reported through the `metrics` crate when the `metrics` feature is on. Without
+//! it every function here is empty and inlines away.
+//!
+//! Nothing is recorded until the application installs a `metrics` recorder (an exporter).
+
+/// Calls to `solve_with_memory` and `solve_one_bump`.
+#[cfg(feature = "metrics")]
+pub const SOLVES_ATTEMPTED: &str = "packx_solves_attempted";
+/// Calls to `solve_with_memory` and `solve_one_bump` that found a solution.
+#[cfg(feature = "metrics")]
+pub const SOLVES_SUCCEEDED: &str = "packx_solves_succeeded";
+/// Seed combinations hashed by the odometer-based solvers.
+#[cfg(feature = "metrics")]
+pub const COMBINATIONS_TRIED: &str = "packx_combinations_tried";
+/// Memories built by the `build_memory*` functions, including in-place rebuilds by
+/// `build_memory_into` and partial ones by `build_memory_range`. Loading or merging
+/// existing tables doesn't count.
+#[cfg(feature = "metrics")]
+pub const MEMORIES_BUILT: &str = "packx_memories_built";
+
+#[inline(always)]
+pub(crate) fn solve_finished(_found: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(SOLVES_ATTEMPTED).increment(1);
+        if _found {
+            metrics::counter!(SOLVES_SUCCEEDED).increment(1);
+        }
+    }
+}
+
+#[inline(always)]
+pub(crate) fn combinations_tried(_count: u64) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(COMBINATIONS_TRIED).increment(_count);
+}
+
+#[inline(always)]
+pub(crate) fn memory_built() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(MEMORIES_BUILT).increment(1);
+}
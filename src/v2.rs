@@ -0,0 +1,173 @@
+//! Solutions with u16 nonces.
+//!
+//! With u8 nonces a seed tries only 256 hashes and usually misses some targets, so a chunk
+//! can fail to pack on every bump. Widening the nonce to u16 lets table building keep hashing
+//! until each seed reaches all 256 targets, at the cost of a 273-byte solution and twice the
+//! nonce storage per table. The hash input is `[pubkey, bump, seed, nonce as 2 LE bytes]`,
+//! BLAKE3 with the target at byte 0; V2 solutions are not interchangeable with `Solution`.
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{compute_hash, get_difficulty};
+#[cfg(feature = "alloc")]
+use crate::{bit_set, bit_test, covers_all, group_need, MixedRadixCounter};
+
+pub const SOLUTION_V2_SIZE: usize = 273; // 1 (bump) + 16 (seeds) + 256 (128 u16 nonces)
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolutionV2 {
+    pub bump: u8,
+    pub seeds: [u8; 16],
+    pub nonces: [u16; 128],
+}
+
+impl SolutionV2 {
+    /// Leading-zero bits in BLAKE3 of the serialized solution.
+    pub fn difficulty(&self) -> u32 {
+        get_difficulty(compute_hash(&[&serialize_v2(self)]))
+    }
+}
+
+/// Bump, seeds, then each nonce little-endian.
+pub fn serialize_v2(solution: &SolutionV2) -> [u8; SOLUTION_V2_SIZE] {
+    let mut out = [0u8; SOLUTION_V2_SIZE];
+    out[0] = solution.bump;
+    out[1..17].copy_from_slice(&solution.seeds);
+    for (dst, nonce) in out[17..].chunks_exact_mut(2).zip(&solution.nonces) {
+        dst.copy_from_slice(&nonce.to_le_bytes());
+    }
+    out
+}
+
+pub fn deserialize_v2(bytes: &[u8; SOLUTION_V2_SIZE]) -> SolutionV2 {
+    let mut solution = SolutionV2 { bump: bytes[0], seeds: [0u8; 16], nonces: [0u16; 128] };
+    solution.seeds.copy_from_slice(&bytes[1..17]);
+    for (nonce, src) in solution.nonces.iter_mut().zip(bytes[17..].chunks_exact(2)) {
+        *nonce = u16::from_le_bytes([src[0], src[1]]);
+    }
+    solution
+}
+
+/// Target byte for (pubkey, bump, seed, nonce) with a u16 nonce.
+#[inline(always)]
+pub fn h0_v2(pubkey: &[u8; 32], bump: u8, seed: u8, nonce: u16) -> u8 {
+    compute_hash(&[pubkey, &[bump], &[seed], &nonce.to_le_bytes()])[0]
+}
+
+pub fn unpack_v2(pubkey: &[u8; 32], solution: &SolutionV2) -> [u8; 128] {
+    let mut data = [0u8; 128];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = h0_v2(pubkey, solution.bump, solution.seeds[i / 8], solution.nonces[i]);
+    }
+    data
+}
+
+/// Check reconstruction and difficulty.
+pub fn verify_v2(pubkey: &[u8; 32], data: &[u8; 128], solution: &SolutionV2, difficulty: u32) -> bool {
+    unpack_v2(pubkey, solution) == *data && solution.difficulty() >= difficulty
+}
+
+/// Per-bump table with u16 nonces.
+#[cfg(feature = "alloc")]
+pub struct SeedTableV2 {
+    /// [seed][target] -> nonce
+    pub nonces: Box<[[u16; 256]]>, // len = 256
+    /// [seed] -> 256-bit bitset of achievable targets
+    pub present: Box<[[u8; 32]]>,  // len = 256
+}
+
+/// All bumps for one pubkey with u16 nonces (about 34 MiB).
+#[cfg(feature = "alloc")]
+pub struct SolverMemoryV2 {
+    pub tables: Box<[Box<SeedTableV2>]>,
+}
+
+/// Build one bump table, hashing each seed until it reaches every target or runs out of nonces.
+#[cfg(feature = "alloc")]
+pub fn build_one_bump_v2(pubkey: &[u8; 32], bump: u8) -> Box<SeedTableV2> {
+    let mut table = Box::new(SeedTableV2 {
+        nonces: vec![[0u16; 256]; 256].into_boxed_slice(),
+        present: vec![[0u8; 32]; 256].into_boxed_slice(),
+    });
+    let mut input = [0u8; 36];
+    input[..32].copy_from_slice(pubkey);
+    input[32] = bump;
+
+    for seed in 0u8..=u8::MAX {
+        input[33] = seed;
+        let present_row = &mut table.present[seed as usize];
+        let nonces_row = &mut table.nonces[seed as usize];
+
+        let mut covered = 0usize;
+        for nonce in 0u16..=u16::MAX {
+            input[34..].copy_from_slice(&nonce.to_le_bytes());
+            let t = compute_hash(&[&input])[0];
+            if !bit_test(present_row, t) {
+                bit_set(present_row, t);
+                nonces_row[t as usize] = nonce;
+                covered += 1;
+                if covered == 256 {
+                    break;
+                }
+            }
+        }
+    }
+
+    table
+}
+
+#[cfg(feature = "alloc")]
+pub fn build_memory_v2(pubkey: &[u8; 32]) -> SolverMemoryV2 {
+    let tables: Vec<Box<SeedTableV2>> = (0u8..=u8::MAX).map(|bump| build_one_bump_v2(pubkey, bump)).collect();
+    SolverMemoryV2 { tables: tables.into_boxed_slice() }
+}
+
+/// Solve for one bump by trying combinations of covering seeds, fewest-candidate groups first.
+#[cfg(feature = "alloc")]
+pub fn solve_one_bump_v2(data: &[u8; 128], bump: u8, table: &SeedTableV2, difficulty: u32) -> Option<SolutionV2> {
+    let cands: [Vec<(u8, [u16; 8])>; 16] = core::array::from_fn(|g| {
+        let need = group_need(data, g, 8);
+        (0u8..=u8::MAX)
+            .filter(|&seed| covers_all(&table.present[seed as usize], &need))
+            .map(|seed| {
+                let row = &table.nonces[seed as usize];
+                (seed, need.map(|t| row[t as usize]))
+            })
+            .collect()
+    });
+
+    let mut order: [usize; 16] = core::array::from_fn(|i| i);
+    order.sort_by_key(|&g| (cands[g].len(), g));
+    let radices: [usize; 16] = core::array::from_fn(|i| cands[order[i]].len());
+
+    for idxs in MixedRadixCounter::new(radices)? {
+        let mut solution = SolutionV2 { bump, seeds: [0u8; 16], nonces: [0u16; 128] };
+        for (pos, &g) in order.iter().enumerate() {
+            let (seed, nonces8) = cands[g][idxs[pos]];
+            solution.seeds[g] = seed;
+            solution.nonces[g * 8..g * 8 + 8].copy_from_slice(&nonces8);
+        }
+        if solution.difficulty() >= difficulty {
+            return Some(solution);
+        }
+    }
+
+    None
+}
+
+/// Try bumps in order 0..=255 and return the first solution meeting `difficulty`.
+#[cfg(feature = "alloc")]
+pub fn solve_with_memory_v2(data: &[u8; 128], mem: &SolverMemoryV2, difficulty: u32) -> Option<SolutionV2> {
+    mem.tables
+        .iter()
+        .enumerate()
+        .find_map(|(bump, table)| solve_one_bump_v2(data, bump as u8, table, difficulty))
+}
+
+/// Build all u16-nonce tables and solve.
+#[cfg(feature = "alloc")]
+pub fn solve_v2(pubkey: &[u8; 32], data: &[u8; 128], difficulty: u32) -> Option<SolutionV2> {
+    let mem = build_memory_v2(pubkey);
+    solve_with_memory_v2(data, &mem, difficulty)
+}
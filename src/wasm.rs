@@ -0,0 +1,19 @@
+//! JavaScript-facing entry points for `wasm32-unknown-unknown` builds via wasm-bindgen.
+
+use alloc::vec::Vec;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{solve, SOLUTION_SIZE};
+
+/// Solve one 128-byte chunk for `pubkey`, returning the 145-byte serialized solution.
+///
+/// Returns None if `pubkey` isn't 32 bytes, `data` isn't 128 bytes, or no solution reaches
+/// `difficulty`. Builds the full table set on every call.
+#[wasm_bindgen]
+pub fn solve_wasm(pubkey: &[u8], data: &[u8], difficulty: u32) -> Option<Vec<u8>> {
+    let pubkey: &[u8; 32] = pubkey.try_into().ok()?;
+    let data: &[u8; 128] = data.try_into().ok()?;
+    let solution = solve(pubkey, data, difficulty)?;
+    let bytes: [u8; SOLUTION_SIZE] = solution.into();
+    Some(bytes.to_vec())
+}